@@ -0,0 +1,5 @@
+use std::{env, process::ExitCode};
+
+fn main() -> ExitCode {
+    seq::multicall(&env::args().collect::<Vec<_>>())
+}
@@ -1,11 +1,6 @@
-use std::{env, io::stdout, process};
+use seq::{crab_core, SeqUtility};
+use std::{env, process::ExitCode};
 
-#[path = "seq/seq.rs"]
-mod seq;
-
-fn main() {
-    if let Err(err) = seq::run(&mut stdout(), &env::args().skip(1).collect::<Vec<_>>()) {
-        eprintln!("{}", err);
-        process::exit(2);
-    }
+fn main() -> ExitCode {
+    crab_core::exec(&SeqUtility, &env::args().skip(1).collect::<Vec<_>>())
 }
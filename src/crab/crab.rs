@@ -0,0 +1,186 @@
+use crate::crab_core::{self, CrabError, Utility};
+use crate::seq::SeqUtility;
+use getopts::Options;
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::Path,
+    process::ExitCode,
+};
+
+/// The utilities of the crab multicall binary.
+pub const UTILITIES: &[&dyn Utility] = &[&SeqUtility];
+
+macro_rules! usage {
+    () => {
+        "Usage: crab utility [argument ...]\n       crab --install-links dir"
+    };
+}
+
+// Command identifies what the crab binary was asked to do.
+#[derive(Debug)]
+enum Command<'a> {
+    // Run a utility with the arguments that follow its name.
+    Run(&'static dyn Utility, &'a [String]),
+    // Link the utilities into a directory.
+    InstallLinks(&'a str),
+    Help,
+    Version,
+    // An unknown utility, or no utility at all.
+    Unknown(Option<&'a str>),
+    // A malformed command line, with a message.
+    Usage(String),
+}
+
+/// Returns the utility name, if crab has one.
+pub fn utility(name: &str) -> Option<&'static dyn Utility> {
+    UTILITIES.iter().find(|u| u.name() == name).copied()
+}
+
+/// Runs the crab multicall binary with the command line arguments, including
+/// the program name, and returns the status with which to exit. When invoked
+/// by the name of a utility, such as through a link named `seq`, runs that
+/// utility; otherwise the first argument names the utility, as in
+/// `crab seq 1 10`. `crab --install-links dir` links every utility to the
+/// crab executable in dir.
+pub fn multicall(argv: &[String]) -> ExitCode {
+    match command(argv) {
+        Command::Run(utility, args) => crab_core::exec(utility, args),
+        Command::InstallLinks(dir) => crab_core::report(
+            &mut io::stderr(),
+            env::current_exe()
+                .and_then(|exe| install_links(Path::new(dir), &exe))
+                .map_err(|err| CrabError::Failure {
+                    name: "crab",
+                    message: err.to_string(),
+                }),
+        ),
+        Command::Help => {
+            let mut out = crab_core::stdout();
+            crab_core::report(&mut io::stderr(), help_or_version(&mut out, "--help"))
+        }
+        Command::Version => {
+            let mut out = crab_core::stdout();
+            crab_core::report(&mut io::stderr(), help_or_version(&mut out, "--version"))
+        }
+        Command::Unknown(name) => crab_core::report(
+            &mut io::stderr(),
+            Err(CrabError::Usage {
+                name: "crab",
+                message: name.map(|name| format!("unknown utility: {}", name)),
+                synopsis: usage!(),
+            }),
+        ),
+        Command::Usage(message) => crab_core::report(
+            &mut io::stderr(),
+            Err(CrabError::Usage {
+                name: "crab",
+                message: Some(message),
+                synopsis: usage!(),
+            }),
+        ),
+    }
+}
+
+// Determines the command from the program name or, if it is not the name of
+// a utility, from the first argument.
+fn command(argv: &[String]) -> Command<'_> {
+    if let Some(utility) = argv.first().and_then(|arg0| utility(progname(arg0))) {
+        return Command::Run(utility, &argv[1..]);
+    }
+
+    let args = argv.get(1..).unwrap_or_default();
+    match args.first().map(String::as_str) {
+        Some("--help") => Command::Help,
+        Some("--version") => Command::Version,
+        Some("--install-links") => match &args[1..] {
+            [dir] => Command::InstallLinks(dir),
+            _ => Command::Usage("--install-links requires one directory".into()),
+        },
+        Some(arg) if arg.starts_with("--install-links=") => match &args[1..] {
+            [] => Command::InstallLinks(&arg["--install-links=".len()..]),
+            _ => Command::Usage("--install-links requires one directory".into()),
+        },
+        Some(arg) if arg.starts_with('-') => {
+            Command::Usage(format!("Unrecognized option: '{}'", arg))
+        }
+        Some(name) => match utility(name) {
+            Some(utility) => Command::Run(utility, &args[1..]),
+            None => Command::Unknown(Some(name)),
+        },
+        None => Command::Unknown(None),
+    }
+}
+
+// Writes the help, which lists the utilities, or the version of crab to out,
+// as flag requests, in the same way as the utilities do.
+fn help_or_version(out: &mut dyn Write, flag: &str) -> Result<(), CrabError> {
+    let mut opts = Options::new();
+    opts.optopt(
+        "",
+        "install-links",
+        "link each utility to the crab executable in dir",
+        "dir",
+    );
+    crab_core::standard_options(&mut opts);
+    let matches = opts.parse([flag]).map_err(|e| CrabError::Usage {
+        name: "crab",
+        message: Some(e.to_string()),
+        synopsis: usage!(),
+    })?;
+
+    let names: Vec<&str> = UTILITIES.iter().map(|u| u.name()).collect();
+    let synopsis = format!("{}\n\nUtilities:\n    {}", usage!(), names.join("\n    "));
+    crab_core::help_or_version(out, "crab", &synopsis, &opts, &matches)
+        .and_then(|_| out.flush())
+        .map_err(|err| CrabError::Io {
+            name: "crab",
+            context: "write error",
+            err,
+        })
+}
+
+// Returns the file name of the program, without any executable suffix, so
+// that `/usr/local/bin/seq` and `seq.exe` both name seq.
+fn progname(arg0: &str) -> &str {
+    let name = Path::new(arg0)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(arg0);
+    name.strip_suffix(env::consts::EXE_SUFFIX)
+        .filter(|name| !name.is_empty())
+        .unwrap_or(name)
+}
+
+/// Creates a symbolic link to exe in dir for every utility, named for the
+/// utility. A link that already points to exe is left as it is; any other
+/// file of the same name is an error.
+pub fn install_links(dir: &Path, exe: &Path) -> io::Result<()> {
+    for utility in UTILITIES {
+        let link = dir.join(format!("{}{}", utility.name(), env::consts::EXE_SUFFIX));
+        if fs::read_link(&link).ok().as_deref() == Some(exe) {
+            continue;
+        }
+        symlink(exe, &link).map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("cannot link {}: {}", link.display(), err),
+            )
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(exe: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(exe, link)
+}
+
+#[cfg(windows)]
+fn symlink(exe: &Path, link: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_file(exe, link)
+}
+
+#[path = "crab_test.rs"]
+#[cfg(test)]
+mod test;
@@ -0,0 +1,117 @@
+use super::*;
+
+#[test]
+fn test_utility() {
+    assert_eq!(utility("seq").map(|u| u.name()), Some("seq"));
+    for name in ["", "crab", "cat", "SEQ"] {
+        assert!(utility(name).is_none(), "Should not find {:?}", name);
+    }
+}
+
+#[test]
+fn test_progname() {
+    for (arg0, exp) in [
+        ("seq", "seq"),
+        ("/usr/local/bin/seq", "seq"),
+        ("./crab", "crab"),
+        (&format!("bin/seq{}", env::consts::EXE_SUFFIX), "seq"),
+        ("", ""),
+    ] {
+        assert_eq!(progname(arg0), exp, "Should get name of {:?}", arg0);
+    }
+}
+
+#[test]
+fn test_command() {
+    for (argv, exp) in [
+        (vec!["seq", "1", "3"], "Run(seq, [\"1\", \"3\"])"),
+        (vec!["/bin/seq"], "Run(seq, [])"),
+        (
+            vec!["crab", "seq", "-w", "10"],
+            "Run(seq, [\"-w\", \"10\"])",
+        ),
+        (vec!["/opt/crab", "seq"], "Run(seq, [])"),
+        (vec!["seq", "seq", "3"], "Run(seq, [\"seq\", \"3\"])"),
+        (vec!["crab", "--help"], "Help"),
+        (vec!["crab", "--version", "seq"], "Version"),
+        (
+            vec!["crab", "--install-links", "/bin"],
+            "InstallLinks(\"/bin\")",
+        ),
+        (
+            vec!["crab", "--install-links=/bin"],
+            "InstallLinks(\"/bin\")",
+        ),
+        (
+            vec!["crab", "--install-links"],
+            "Usage(\"--install-links requires one directory\")",
+        ),
+        (
+            vec!["crab", "--install-links", "a", "b"],
+            "Usage(\"--install-links requires one directory\")",
+        ),
+        (vec!["crab", "-x"], "Usage(\"Unrecognized option: '-x'\")"),
+        (vec!["crab", "cat", "foo"], "Unknown(Some(\"cat\"))"),
+        (vec!["crab"], "Unknown(None)"),
+        (vec![], "Unknown(None)"),
+    ] {
+        let argv: Vec<String> = argv.into_iter().map(String::from).collect();
+        let cmd = format!("{:?}", command(&argv));
+        assert_eq!(cmd, exp, "Invalid command for {:?}", argv);
+    }
+}
+
+#[test]
+fn test_help_or_version() {
+    for (flag, exp) in [
+        ("--help", "Usage: crab utility [argument ...]\n"),
+        ("--help", "\nUtilities:\n    seq\n"),
+        ("--help", "--install-links dir"),
+        (
+            "--version",
+            concat!("crab ", env!("CARGO_PKG_VERSION"), "\n"),
+        ),
+    ] {
+        let mut out: Vec<u8> = vec![];
+        help_or_version(&mut out, flag).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(
+            out.contains(exp),
+            "Should write {:?} for {}: {}",
+            exp,
+            flag,
+            out
+        );
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn test_install_links() -> io::Result<()> {
+    let dir = env::temp_dir().join(format!("crab-install-links-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let exe = dir.join("crab");
+    fs::write(&exe, "")?;
+
+    install_links(&dir, &exe)?;
+    for utility in UTILITIES {
+        let link = dir.join(format!("{}{}", utility.name(), env::consts::EXE_SUFFIX));
+        assert_eq!(fs::read_link(&link)?, exe, "Should link {:?}", utility);
+    }
+
+    // Installing again leaves the links as they are.
+    install_links(&dir, &exe)?;
+
+    // Any other file is an error.
+    let res = install_links(&dir, &dir.join("other"));
+    fs::remove_dir_all(&dir)?;
+    match res {
+        Err(err) => assert!(
+            err.to_string().starts_with("cannot link "),
+            "Unexpected error {}",
+            err
+        ),
+        Ok(()) => panic!("Should fail to replace existing links"),
+    }
+    Ok(())
+}
@@ -0,0 +1,188 @@
+//! Shared support for crab utilities: the [`Utility`] trait that every
+//! utility implements, the [`CrabError`] type through which utilities report
+//! errors, standard option handling, and the standard I/O setup with which
+//! [`exec`] runs a utility as a program.
+
+use getopts::{Matches, Options};
+use std::{
+    error, fmt,
+    io::{self, BufWriter, StdoutLock, Write},
+    process::ExitCode,
+};
+
+/// Utility is a crab utility, such as seq.
+pub trait Utility {
+    /// Returns the name of the utility, with which its errors are prefixed.
+    fn name(&self) -> &'static str;
+
+    /// Runs the utility with the command line arguments, not including the
+    /// program name. Writes output to out and errors to err, and returns the
+    /// status with which to exit. Flushes out before returning.
+    fn run(&self, out: &mut dyn Write, err: &mut dyn Write, argv: &[String]) -> ExitCode;
+}
+
+impl fmt::Debug for dyn Utility + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// CrabError is an error from a utility. Its message is formatted as the
+/// utility prints it, prefixed with the name of the utility, as in
+/// `seq: zero increment`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CrabError {
+    /// The command line is invalid. Without a message, the error displays
+    /// the usage synopsis of the utility.
+    Usage {
+        name: &'static str,
+        message: Option<String>,
+        synopsis: &'static str,
+    },
+    /// The utility failed, as for an invalid argument.
+    Failure { name: &'static str, message: String },
+    /// Reading or writing failed. The context describes the operation, as
+    /// in `write error`.
+    Io {
+        name: &'static str,
+        context: &'static str,
+        err: io::Error,
+    },
+}
+
+impl CrabError {
+    /// Returns the name of the utility that failed.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CrabError::Usage { name, .. }
+            | CrabError::Failure { name, .. }
+            | CrabError::Io { name, .. } => name,
+        }
+    }
+
+    /// Returns the status with which a utility exits on the error: 2 for
+    /// usage errors, 3 for I/O errors, and 1 for any other error.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CrabError::Usage { .. } => 2,
+            CrabError::Io { .. } => 3,
+            CrabError::Failure { .. } => 1,
+        }
+    }
+
+    /// Reports whether the error is a write to a closed pipe, as when the
+    /// output of `seq 1 inf` is piped to `head`.
+    pub fn is_broken_pipe(&self) -> bool {
+        matches!(self, CrabError::Io { err, .. } if err.kind() == io::ErrorKind::BrokenPipe)
+    }
+}
+
+impl fmt::Display for CrabError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CrabError::Usage {
+                message: None,
+                synopsis,
+                ..
+            } => write!(f, "{}", synopsis),
+            CrabError::Usage {
+                name,
+                message: Some(msg),
+                ..
+            }
+            | CrabError::Failure { name, message: msg } => write!(f, "{}: {}", name, msg),
+            CrabError::Io { name, context, err } => write!(f, "{}: {}: {}", name, context, err),
+        }
+    }
+}
+
+impl error::Error for CrabError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            CrabError::Io { err, .. } => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Reports the result of running a utility: writes any error to err,
+/// followed for usage errors by a pointer to `--help`, and returns the
+/// status with which to exit. A write to a closed pipe ends the utility
+/// quietly and successfully.
+pub fn report(err: &mut dyn Write, res: Result<(), CrabError>) -> ExitCode {
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) if e.is_broken_pipe() => ExitCode::SUCCESS,
+        Err(e) => {
+            // There is nowhere left to report a failure to write an error.
+            let _ = writeln!(err, "{}", e);
+            if let CrabError::Usage { name, .. } = e {
+                let _ = writeln!(err, "Try '{} --help' for more information.", name);
+            }
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
+
+/// Adds the `--help` and `--version` flags that every utility accepts.
+/// Utilities add them after their own options, so that they are listed last.
+pub fn standard_options(opts: &mut Options) -> &mut Options {
+    opts.optflag("", "help", "print this help and exit");
+    opts.optflag("", "version", "print the version and exit")
+}
+
+/// Handles the `--help` and `--version` flags added by [`standard_options`].
+/// If either is set, writes the help, starting with the usage synopsis, or
+/// the version of the utility name to out and returns true. The name `crab`
+/// stands for the crab binary itself.
+pub fn help_or_version(
+    out: &mut dyn Write,
+    name: &str,
+    synopsis: &str,
+    opts: &Options,
+    matches: &Matches,
+) -> io::Result<bool> {
+    if matches.opt_present("help") {
+        write!(out, "{}", opts.usage(synopsis))?;
+    } else if matches.opt_present("version") {
+        match name {
+            "crab" => writeln!(out, "crab {}", env!("CARGO_PKG_VERSION"))?,
+            _ => writeln!(out, "crab {} {}", name, env!("CARGO_PKG_VERSION"))?,
+        }
+    } else {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Returns standard output, locked and buffered, so that utilities writing
+/// many small items make few system calls.
+pub fn stdout() -> BufWriter<StdoutLock<'static>> {
+    BufWriter::with_capacity(64 * 1024, io::stdout().lock())
+}
+
+/// Restores the default disposition of SIGPIPE, which Rust programs ignore,
+/// so that a utility writing to a closed pipe ends at once, as other Unix
+/// utilities do, rather than failing its next write.
+pub fn sigpipe() {
+    #[cfg(unix)]
+    // SAFETY: Setting a signal to its default disposition installs no
+    // handler, and runs before the utility starts any threads.
+    unsafe {
+        libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+    }
+}
+
+/// Runs a utility as a program with the command line arguments, not
+/// including the program name: restores SIGPIPE, runs the utility with
+/// buffered standard output and unbuffered standard error, and returns the
+/// status with which to exit.
+pub fn exec(utility: &dyn Utility, argv: &[String]) -> ExitCode {
+    sigpipe();
+    utility.run(&mut stdout(), &mut io::stderr().lock(), argv)
+}
+
+#[path = "crab_core_test.rs"]
+#[cfg(test)]
+mod test;
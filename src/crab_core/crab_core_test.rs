@@ -0,0 +1,119 @@
+use super::*;
+
+#[test]
+fn test_error() {
+    for (err, msg, code) in [
+        (
+            CrabError::Usage {
+                name: "seq",
+                message: None,
+                synopsis: "Usage: seq last",
+            },
+            "Usage: seq last",
+            2,
+        ),
+        (
+            CrabError::Usage {
+                name: "seq",
+                message: Some("Unrecognized option: 'z'".into()),
+                synopsis: "Usage: seq last",
+            },
+            "seq: Unrecognized option: 'z'",
+            2,
+        ),
+        (
+            CrabError::Failure {
+                name: "seq",
+                message: "zero increment".into(),
+            },
+            "seq: zero increment",
+            1,
+        ),
+        (
+            CrabError::Io {
+                name: "crab",
+                context: "write error",
+                err: io::Error::new(io::ErrorKind::WriteZero, "disk full"),
+            },
+            "crab: write error: disk full",
+            3,
+        ),
+    ] {
+        assert_eq!(err.to_string(), msg, "Should display {:?}", err);
+        assert_eq!(err.exit_code(), code, "Should get exit code of {:?}", err);
+        assert!(msg.contains(err.name()), "Should get name of {:?}", err);
+    }
+}
+
+#[test]
+fn test_report() {
+    for (res, exp, code) in [
+        (Ok(()), "", ExitCode::SUCCESS),
+        (
+            Err(CrabError::Failure {
+                name: "seq",
+                message: "zero increment".into(),
+            }),
+            "seq: zero increment\n",
+            ExitCode::from(1),
+        ),
+        (
+            Err(CrabError::Usage {
+                name: "seq",
+                message: None,
+                synopsis: "Usage: seq last",
+            }),
+            "Usage: seq last\nTry 'seq --help' for more information.\n",
+            ExitCode::from(2),
+        ),
+        (
+            Err(CrabError::Io {
+                name: "seq",
+                context: "write error",
+                err: io::Error::from(io::ErrorKind::BrokenPipe),
+            }),
+            "",
+            ExitCode::SUCCESS,
+        ),
+    ] {
+        let mut err: Vec<u8> = vec![];
+        assert_eq!(report(&mut err, res), code, "Should exit for {:?}", exp);
+        assert_eq!(String::from_utf8(err).unwrap(), exp);
+    }
+}
+
+#[test]
+fn test_help_or_version() -> io::Result<()> {
+    let mut opts = Options::new();
+    opts.optflag("w", "equal-width", "equalize widths");
+    standard_options(&mut opts);
+    for (args, done, exp) in [
+        (vec!["-w"], false, String::new()),
+        (vec!["--help"], true, opts.usage("Usage: seq last")),
+        (
+            vec!["--version"],
+            true,
+            format!("crab seq {}\n", env!("CARGO_PKG_VERSION")),
+        ),
+    ] {
+        let matches = opts.parse(&args).unwrap();
+        let mut out: Vec<u8> = vec![];
+        assert_eq!(
+            help_or_version(&mut out, "seq", "Usage: seq last", &opts, &matches)?,
+            done,
+            "Should handle {:?}",
+            args
+        );
+        assert_eq!(String::from_utf8(out).unwrap(), exp);
+    }
+
+    // The crab binary has no utility name.
+    let mut out: Vec<u8> = vec![];
+    let matches = opts.parse(["--version"]).unwrap();
+    help_or_version(&mut out, "crab", "Usage: crab", &opts, &matches)?;
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        format!("crab {}\n", env!("CARGO_PKG_VERSION"))
+    );
+    Ok(())
+}
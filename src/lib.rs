@@ -0,0 +1,26 @@
+//! The crab seq utility as a library. [`Seq`] builds sequences of numbers
+//! with the same semantics as the `seq` command, [`SeqIter`] iterates over
+//! them, and [`run`] runs the command itself. [`multicall`] runs the `crab`
+//! binary, which dispatches to every utility, and [`crab_core`] holds what
+//! the utilities share.
+//!
+//! ```
+//! use seq::Seq;
+//!
+//! let nums: Vec<f64> = Seq::new(1.0, 2.0).incr(0.5).iter()?.collect();
+//! assert_eq!(nums, [1.0, 1.5, 2.0]);
+//!
+//! let text = Seq::new(1.0, 2.0).incr(0.5).separator(",").display()?;
+//! assert_eq!(text.to_string(), "1.0,1.5,2.0,");
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+#[path = "crab/crab.rs"]
+mod crab;
+#[path = "crab_core/crab_core.rs"]
+pub mod crab_core;
+#[path = "seq/seq.rs"]
+mod seq;
+
+pub use crab::{install_links, multicall, utility, UTILITIES};
+pub use seq::{run, Result, Seq, SeqDisplay, SeqError, SeqIter, SeqSpec, SeqUtility};
@@ -0,0 +1,363 @@
+use super::{Result, SeqError};
+use std::convert::TryFrom;
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// DateTime is a date and time in UTC between the years 0000 and 9999,
+/// stored as seconds since 1970-01-01T00:00:00Z.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime(i64);
+
+impl DateTime {
+    /// Parses an ISO 8601 date such as `2026-01-31`, or a date and time such
+    /// as `2026-01-31T12:30`, `2026-01-31T12:30:15`, or `2026-01-31 12:30Z`.
+    /// Returns the date and whether it has a time.
+    pub fn parse(s: &str) -> Option<(DateTime, bool)> {
+        let s = s.strip_suffix(['Z', 'z']).unwrap_or(s);
+        let (date, time) = match s.find(['T', 't', ' ']) {
+            Some(idx) => (&s[..idx], Some(&s[idx + 1..])),
+            None => (s, None),
+        };
+
+        let mut fields = date.split('-');
+        let year = field(fields.next()?, 4, 0, 9999)?;
+        let month = field(fields.next()?, 2, 1, 12)?;
+        let day = field(fields.next()?, 2, 1, days_in_month(year, month))?;
+        if fields.next().is_some() {
+            return None;
+        }
+
+        let mut secs = from_civil(year, month, day) * DAY;
+        if let Some(time) = time {
+            let mut fields = time.split(':');
+            secs += field(fields.next()?, 2, 0, 23)? * HOUR;
+            secs += field(fields.next()?, 2, 0, 59)? * MINUTE;
+            if let Some(sec) = fields.next() {
+                secs += field(sec, 2, 0, 59)?;
+            }
+            if fields.next().is_some() {
+                return None;
+            }
+        }
+        Some((DateTime(secs), time.is_some()))
+    }
+
+    /// Returns the number of seconds since 1970-01-01T00:00:00Z.
+    pub fn timestamp(&self) -> i64 {
+        self.0
+    }
+
+    /// Returns the date advanced n times by the duration, or None if the
+    /// result falls outside the years 0000 through 9999. Adding months keeps
+    /// the day of the month, or uses the last day of a shorter month, so that
+    /// adding one month to January 31 gives the last day of February.
+    pub fn add(&self, d: &Duration, n: u64) -> Option<DateTime> {
+        let n = i64::try_from(n).ok()?;
+        let (days, secs) = (self.0.div_euclid(DAY), self.0.rem_euclid(DAY));
+        let (year, month, day) = to_civil(days);
+
+        let months = (year * 12 + month - 1).checked_add(d.months.checked_mul(n)?)?;
+        let (year, month) = (months.div_euclid(12), months.rem_euclid(12) + 1);
+        if !(0..=9999).contains(&year) {
+            return None;
+        }
+        let day = day.min(days_in_month(year, month));
+
+        let secs =
+            (from_civil(year, month, day) * DAY + secs).checked_add(d.secs.checked_mul(n)?)?;
+        if !(from_civil(0, 1, 1) * DAY..from_civil(10000, 1, 1) * DAY).contains(&secs) {
+            return None;
+        }
+        Some(DateTime(secs))
+    }
+}
+
+/// Duration is an amount of time in months and seconds. Both have the same
+/// sign.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Duration {
+    months: i64,
+    secs: i64,
+}
+
+impl Duration {
+    /// One day.
+    pub const DAY: Duration = Duration {
+        months: 0,
+        secs: DAY,
+    };
+
+    /// Parses an ISO 8601 duration such as `P1D`, `P1Y2M`, `PT12H`, or
+    /// `P2W`, or a shorthand made of numbers and units such as `1d`, `1mo`, or
+    /// `1h30min`. The units are `y`, `mo`, `w`, `d`, `h`, `min`, and `s`.
+    /// Either form may have a leading `-`.
+    pub fn parse(s: &str) -> Option<Duration> {
+        let (neg, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+
+        let mut d = Duration::default();
+        let mut time = false;
+        let mut iso = false;
+        let mut rest = s;
+        if let Some(s) = rest.strip_prefix(['P', 'p']) {
+            iso = true;
+            rest = s;
+        }
+        if rest.is_empty() {
+            return None;
+        }
+
+        while !rest.is_empty() {
+            if iso && !time {
+                if let Some(s) = rest.strip_prefix(['T', 't']) {
+                    time = true;
+                    rest = s;
+                    if rest.is_empty() {
+                        return None;
+                    }
+                    continue;
+                }
+            }
+
+            let idx = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let num: i64 = rest[..idx].parse().ok()?;
+            rest = &rest[idx..];
+            // ISO 8601 units are single letters, which may run into a T.
+            let idx = match rest.find(|c: char| !c.is_ascii_alphabetic()) {
+                _ if iso => rest.len().min(1),
+                Some(idx) => idx,
+                None => rest.len(),
+            };
+            let unit = rest[..idx].to_ascii_lowercase();
+            rest = &rest[idx..];
+
+            let (months, secs) = match (iso, time, unit.as_str()) {
+                (true, false, "y") | (false, _, "y") => (12, 0),
+                (true, false, "m") | (false, _, "mo") => (1, 0),
+                (true, false, "w") | (false, _, "w") => (0, 7 * DAY),
+                (true, false, "d") | (false, _, "d") => (0, DAY),
+                (true, true, "h") | (false, _, "h") => (0, HOUR),
+                (true, true, "m") | (false, _, "min") => (0, MINUTE),
+                (true, true, "s") | (false, _, "s") => (0, 1),
+                _ => return None,
+            };
+            d.months = d.months.checked_add(num.checked_mul(months)?)?;
+            d.secs = d.secs.checked_add(num.checked_mul(secs)?)?;
+        }
+
+        if neg {
+            d.months = -d.months;
+            d.secs = -d.secs;
+        }
+        Some(d)
+    }
+
+    /// Returns true if the duration is zero.
+    pub fn is_zero(&self) -> bool {
+        self.months == 0 && self.secs == 0
+    }
+
+    /// Returns true if the duration is less than zero.
+    pub fn is_negative(&self) -> bool {
+        self.months < 0 || self.secs < 0
+    }
+
+    /// Returns true if the duration is a whole number of days.
+    pub fn is_whole_days(&self) -> bool {
+        self.secs % DAY == 0
+    }
+
+    /// Returns the approximate length of the duration in seconds, taking a
+    /// month to be an average Gregorian month.
+    pub fn approx_secs(&self) -> f64 {
+        self.months as f64 * 2_629_746.0 + self.secs as f64
+    }
+}
+
+// Elements of a parsed date format.
+#[derive(Debug, PartialEq)]
+enum Item {
+    Literal(String),
+    Field(char),
+}
+
+/// DateFormat is a parsed strftime-style format, as accepted by `seq -f`
+/// with `--date`.
+#[derive(Debug, PartialEq)]
+pub struct DateFormat(Vec<Item>);
+
+impl DateFormat {
+    /// Parses a strftime-style format. The supported conversions are `%Y`,
+    /// `%C`, `%y`, `%m`, `%d`, `%e`, `%j`, `%H`, `%I`, `%p`, `%M`, `%S`, `%a`,
+    /// `%A`, `%b`, `%h`, `%B`, `%u`, `%w`, `%s`, `%F`, `%T`, `%D`, `%R`, and
+    /// `%%` for a literal percent sign.
+    pub fn parse(fmt: &str) -> Result<DateFormat> {
+        let mut items = Vec::new();
+        let mut lit = String::new();
+        let mut chars = fmt.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                lit.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('%') => lit.push('%'),
+                Some(
+                    c @ ('Y' | 'C' | 'y' | 'm' | 'd' | 'e' | 'j' | 'H' | 'I' | 'p' | 'M' | 'S'
+                    | 'a' | 'A' | 'b' | 'h' | 'B' | 'u' | 'w' | 's' | 'F' | 'T' | 'D' | 'R'),
+                ) => {
+                    if !lit.is_empty() {
+                        items.push(Item::Literal(std::mem::take(&mut lit)));
+                    }
+                    items.push(Item::Field(c));
+                }
+                Some(c) => {
+                    return Err(SeqError::InvalidFormat(format!(
+                        "date format '{}' has unknown %{} directive",
+                        fmt, c
+                    )))
+                }
+                None => {
+                    return Err(SeqError::InvalidFormat(format!(
+                        "date format '{}' ends in %",
+                        fmt
+                    )))
+                }
+            }
+        }
+        if !lit.is_empty() {
+            items.push(Item::Literal(lit));
+        }
+        Ok(DateFormat(items))
+    }
+
+    /// Formats the date according to the format.
+    pub fn render(&self, t: &DateTime) -> String {
+        let (days, secs) = (t.0.div_euclid(DAY), t.0.rem_euclid(DAY));
+        let (year, month, day) = to_civil(days);
+        let (hour, min, sec) = (secs / HOUR, secs % HOUR / MINUTE, secs % MINUTE);
+        let weekday = (days + 4).rem_euclid(7) as usize;
+
+        let mut s = String::new();
+        for item in &self.0 {
+            match item {
+                Item::Literal(lit) => s.push_str(lit),
+                Item::Field(c) => s.push_str(&match c {
+                    'Y' => format!("{:04}", year),
+                    'C' => format!("{:02}", year / 100),
+                    'y' => format!("{:02}", year % 100),
+                    'm' => format!("{:02}", month),
+                    'd' => format!("{:02}", day),
+                    'e' => format!("{:2}", day),
+                    'j' => format!("{:03}", days - from_civil(year, 1, 1) + 1),
+                    'H' => format!("{:02}", hour),
+                    'I' => format!("{:02}", (hour + 11) % 12 + 1),
+                    'p' => (if hour < 12 { "AM" } else { "PM" }).to_string(),
+                    'M' => format!("{:02}", min),
+                    'S' => format!("{:02}", sec),
+                    'a' => WEEKDAYS[weekday][..3].to_string(),
+                    'A' => WEEKDAYS[weekday].to_string(),
+                    'b' | 'h' => MONTHS[month as usize - 1][..3].to_string(),
+                    'B' => MONTHS[month as usize - 1].to_string(),
+                    'u' => (if weekday == 0 { 7 } else { weekday }).to_string(),
+                    'w' => weekday.to_string(),
+                    's' => t.0.to_string(),
+                    'F' => format!("{:04}-{:02}-{:02}", year, month, day),
+                    'T' => format!("{:02}:{:02}:{:02}", hour, min, sec),
+                    'D' => format!("{:02}/{:02}/{:02}", month, day, year % 100),
+                    _ => format!("{:02}:{:02}", hour, min),
+                }),
+            }
+        }
+        s
+    }
+}
+
+// Parses a field of exactly len digits between min and max.
+fn field(s: &str, len: usize, min: i64, max: i64) -> Option<i64> {
+    if s.len() != len || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let n = s.parse().ok()?;
+    if n < min || n > max {
+        return None;
+    }
+    Some(n)
+}
+
+// Returns true if the year is a leap year in the proleptic Gregorian calendar.
+fn is_leap(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+// Returns the number of days in a month of a year.
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// Returns the number of days since 1970-01-01 of a date in the proleptic
+// Gregorian calendar, counting years in 400-year eras that start in March so
+// that leap days fall at the end.
+fn from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+// Returns the year, month, and day of a number of days since 1970-01-01.
+fn to_civil(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[path = "date_test.rs"]
+#[cfg(test)]
+mod test;
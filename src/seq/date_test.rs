@@ -0,0 +1,205 @@
+use super::*;
+
+#[test]
+fn test_parse() {
+    for (date, exp) in [
+        ("1970-01-01", (0, false)),
+        ("1970-01-02", (86400, false)),
+        ("1969-12-31", (-86400, false)),
+        ("2000-02-29", (951782400, false)),
+        ("2026-01-01", (1767225600, false)),
+        ("2026-01-01T00:00", (1767225600, true)),
+        ("2026-01-01T12:30:15", (1767270615, true)),
+        ("2026-01-01 12:30Z", (1767270600, true)),
+        ("0000-01-01", (-62167219200, false)),
+        ("9999-12-31T23:59:59", (253402300799, true)),
+    ] {
+        assert_eq!(
+            DateTime::parse(date),
+            Some((DateTime(exp.0), exp.1)),
+            "Should parse {}",
+            date
+        );
+    }
+}
+
+#[test]
+fn test_bad_parse() {
+    for date in [
+        "",
+        "2026",
+        "2026-01",
+        "2026-1-01",
+        "26-01-01",
+        "2026-00-01",
+        "2026-13-01",
+        "2026-01-32",
+        "2026-02-29",
+        "1900-02-29",
+        "2026-04-31",
+        "2026-01-01-01",
+        "2026-01-01T",
+        "2026-01-01T24:00",
+        "2026-01-01T12",
+        "2026-01-01T12:60",
+        "2026-01-01T12:00:60",
+        "2026-01-01T12:00:00:00",
+        "+2026-01-01",
+        "-2026-01-01",
+    ] {
+        assert_eq!(DateTime::parse(date), None, "Should not parse {}", date);
+    }
+}
+
+#[test]
+fn test_add() {
+    let fmt = DateFormat::parse("%FT%T").unwrap();
+    for (date, dur, n, exp) in [
+        ("2026-01-01", "1d", 0, Some("2026-01-01T00:00:00")),
+        ("2026-01-01", "1d", 31, Some("2026-02-01T00:00:00")),
+        ("2026-01-31", "1mo", 1, Some("2026-02-28T00:00:00")),
+        ("2026-01-31", "1mo", 2, Some("2026-03-31T00:00:00")),
+        ("2028-01-31", "P1M", 1, Some("2028-02-29T00:00:00")),
+        ("2024-02-29", "1y", 1, Some("2025-02-28T00:00:00")),
+        ("2024-02-29", "1y", 4, Some("2028-02-29T00:00:00")),
+        ("2026-03-01", "-1d", 1, Some("2026-02-28T00:00:00")),
+        ("2026-03-31", "-1mo", 1, Some("2026-02-28T00:00:00")),
+        ("2026-01-01T23:00", "PT2H", 1, Some("2026-01-02T01:00:00")),
+        (
+            "2026-01-31T12:00",
+            "P1M1DT1H",
+            1,
+            Some("2026-03-01T13:00:00"),
+        ),
+        ("2026-01-01", "1h30min", 3, Some("2026-01-01T04:30:00")),
+        ("2026-01-01", "2w", 1, Some("2026-01-15T00:00:00")),
+        ("9999-12-31", "1d", 1, None),
+        ("0000-01-01", "-1s", 1, None),
+        ("2026-01-01", "1y", 8000, None),
+        ("2026-01-01", "1s", u64::MAX, None),
+    ] {
+        let (first, _) = DateTime::parse(date).unwrap();
+        let dur = Duration::parse(dur).unwrap();
+        assert_eq!(
+            first.add(&dur, n).map(|t| fmt.render(&t)).as_deref(),
+            exp,
+            "Should add {:?} {} times to {}",
+            dur,
+            n,
+            date
+        );
+    }
+}
+
+#[test]
+fn test_parse_duration() {
+    for (dur, months, secs) in [
+        ("1d", 0, 86400),
+        ("+1d", 0, 86400),
+        ("-1d", 0, -86400),
+        ("1D", 0, 86400),
+        ("2w", 0, 1209600),
+        ("1mo", 1, 0),
+        ("1y2mo", 14, 0),
+        ("1h30min", 0, 5400),
+        ("90s", 0, 90),
+        ("P1D", 0, 86400),
+        ("P1Y2M3D", 14, 259200),
+        ("PT1M", 0, 60),
+        ("PT1H30M15S", 0, 5415),
+        ("P1DT12H", 0, 129600),
+        ("-P1M", -1, 0),
+        ("p1w", 0, 604800),
+        ("0d", 0, 0),
+    ] {
+        assert_eq!(
+            Duration::parse(dur),
+            Some(Duration { months, secs }),
+            "Should parse {}",
+            dur
+        );
+    }
+}
+
+#[test]
+fn test_bad_parse_duration() {
+    for dur in [
+        "",
+        "-",
+        "P",
+        "PT",
+        "d",
+        "1",
+        "1m",
+        "1x",
+        "1d-1h",
+        "P1H",
+        "PT1D",
+        "P1DT",
+        "1.5d",
+        "--1d",
+        "P-1D",
+        "1 d",
+        "99999999999999999999d",
+    ] {
+        assert_eq!(Duration::parse(dur), None, "Should not parse {}", dur);
+    }
+}
+
+#[test]
+fn test_render() {
+    let (date, _) = DateTime::parse("2026-03-05T14:07:09").unwrap();
+    for (fmt, exp) in [
+        ("%Y-%m-%d", "2026-03-05"),
+        ("%F %T", "2026-03-05 14:07:09"),
+        ("%C %y %D %R", "20 26 03/05/26 14:07"),
+        ("[%e]", "[ 5]"),
+        ("%j", "064"),
+        ("%I%p", "02PM"),
+        ("%a %A %u %w", "Thu Thursday 4 4"),
+        ("%b %h %B", "Mar Mar March"),
+        ("%s", "1772719629"),
+        ("events_%Y%m%d.log", "events_20260305.log"),
+        ("100%%", "100%"),
+        ("", ""),
+    ] {
+        assert_eq!(
+            DateFormat::parse(fmt).unwrap().render(&date),
+            exp,
+            "Should render {}",
+            fmt
+        );
+    }
+
+    let (date, _) = DateTime::parse("2024-12-31T00:00").unwrap();
+    for (fmt, exp) in [("%j", "366"), ("%I %p", "12 AM"), ("%a %u %w", "Tue 2 2")] {
+        assert_eq!(
+            DateFormat::parse(fmt).unwrap().render(&date),
+            exp,
+            "Should render {}",
+            fmt
+        );
+    }
+}
+
+#[test]
+fn test_bad_format() {
+    for (fmt, err) in [
+        ("%Q", "seq: date format '%Q' has unknown %Q directive"),
+        ("%Y-%", "seq: date format '%Y-%' ends in %"),
+        ("%f", "seq: date format '%f' has unknown %f directive"),
+    ] {
+        match DateFormat::parse(fmt) {
+            Err(e) => assert_eq!(e.to_string(), err, "Should get error for {}", fmt),
+            Ok(_) => panic!("Should get error for {}", fmt),
+        }
+    }
+}
+
+#[test]
+fn test_civil() {
+    for days in (-800_000..3_000_000).step_by(997) {
+        let (y, m, d) = to_civil(days);
+        assert_eq!(from_civil(y, m, d), days, "Should round trip day {}", days);
+    }
+}
@@ -0,0 +1,272 @@
+use std::{borrow::Cow, cmp::Ordering, fmt};
+
+// Each limb of the magnitude holds nine decimal digits.
+const BASE: u32 = 1_000_000_000;
+
+/// The greatest number of digits a parsed decimal may have, to keep huge
+/// exponents such as 1e999999999 from exhausting memory.
+pub const MAX_DIGITS: usize = 4096;
+
+/// Decimal is an exact decimal number, stored as a sign, an arbitrary
+/// precision integer magnitude, and a scale: the number of digits after the
+/// decimal point. Thus 1.50 has the magnitude 150 and the scale 2.
+#[derive(Clone, Debug)]
+pub struct Decimal {
+    neg: bool,
+    // Little-endian limbs in base 10^9, without trailing zero limbs.
+    mag: Vec<u32>,
+    scale: usize,
+}
+
+impl Decimal {
+    /// Parses a decimal literal such as `42`, `-.5`, or `1.25e-3`. Returns
+    /// None for anything else, including infinities, NaN, and hexadecimal.
+    pub fn parse(s: &str) -> Option<Decimal> {
+        let s = s.trim();
+        let (neg, s) = match s.as_bytes().first() {
+            Some(b'-') => (true, &s[1..]),
+            Some(b'+') => (false, &s[1..]),
+            _ => (false, s),
+        };
+        let (num, exp) = match s.find(['e', 'E']) {
+            Some(idx) => (&s[..idx], s[idx + 1..].parse::<i64>().ok()?),
+            None => (s, 0),
+        };
+        let (int, frac) = num.split_once('.').unwrap_or((num, ""));
+        if int.is_empty() && frac.is_empty()
+            || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit())
+        {
+            return None;
+        }
+
+        // Shift the decimal point by the exponent.
+        let scale = (frac.len() as i64).saturating_sub(exp);
+        let digits =
+            ((int.len() + frac.len()) as i64).saturating_add(0.max(scale.saturating_neg()));
+        if digits > MAX_DIGITS as i64 || scale > MAX_DIGITS as i64 {
+            return None;
+        }
+        let mut digits = String::with_capacity(digits as usize);
+        digits.push_str(int);
+        digits.push_str(frac);
+        for _ in scale..0 {
+            digits.push('0');
+        }
+
+        let mut mag = Vec::with_capacity(digits.len() / 9 + 1);
+        let mut end = digits.len();
+        while end > 0 {
+            let start = end.saturating_sub(9);
+            mag.push(digits[start..end].parse().unwrap());
+            end = start;
+        }
+
+        let mut n = Decimal {
+            neg,
+            mag,
+            scale: 0.max(scale) as usize,
+        };
+        n.trim();
+        Some(n)
+    }
+
+    /// Returns the number of digits after the decimal point.
+    pub fn scale(&self) -> usize {
+        self.scale
+    }
+
+    /// Returns true if the number is less than zero.
+    pub fn is_negative(&self) -> bool {
+        self.neg
+    }
+
+    /// Adds other to the number.
+    pub fn add(&mut self, other: &Decimal) {
+        let other = other.rescaled(self.scale);
+        if self.scale < other.scale {
+            *self = self.rescale(other.scale);
+        }
+
+        if self.neg == other.neg {
+            add_mag(&mut self.mag, &other.mag);
+        } else if cmp_mag(&self.mag, &other.mag) != Ordering::Less {
+            sub_mag(&mut self.mag, &other.mag);
+        } else {
+            let mut mag = other.mag.clone();
+            sub_mag(&mut mag, &self.mag);
+            self.mag = mag;
+            self.neg = other.neg;
+        }
+        self.trim();
+    }
+
+    /// Returns the number multiplied by n.
+    pub fn mul(&self, n: u64) -> Decimal {
+        let mut x = self.clone();
+        mul_small(&mut x.mag, n);
+        x.trim();
+        x
+    }
+
+    /// Returns the nearest floating point number.
+    pub fn to_f64(&self) -> f64 {
+        self.to_string().parse().unwrap()
+    }
+
+    /// Formats the absolute value of the number with precision digits after
+    /// the decimal point. The precision must be at least the scale.
+    pub fn to_fixed(&self, precision: usize) -> String {
+        let n = self.rescale(precision);
+        let mut digits = match n.mag.last() {
+            Some(top) => top.to_string(),
+            None => String::new(),
+        };
+        for limb in n.mag.iter().rev().skip(1) {
+            digits.push_str(&format!("{:09}", limb));
+        }
+        if digits.len() <= precision {
+            digits.insert_str(0, &"0".repeat(precision + 1 - digits.len()));
+        }
+        if precision > 0 {
+            digits.insert(digits.len() - precision, '.');
+        }
+        digits
+    }
+
+    /// Returns the number with its magnitude multiplied by a power of ten so
+    /// that it has at least the scale.
+    pub fn rescale(&self, scale: usize) -> Decimal {
+        let mut n = self.clone();
+        let mut shift = scale.saturating_sub(self.scale);
+        while shift > 0 {
+            let step = shift.min(9);
+            mul_small(&mut n.mag, 10u64.pow(step as u32));
+            shift -= step;
+        }
+        n.scale = n.scale.max(scale);
+        n
+    }
+
+    // Like rescale, but borrows the number if it already has the scale.
+    fn rescaled(&self, scale: usize) -> Cow<'_, Decimal> {
+        if scale <= self.scale {
+            Cow::Borrowed(self)
+        } else {
+            Cow::Owned(self.rescale(scale))
+        }
+    }
+
+    // Removes leading zero limbs and the sign of zero.
+    fn trim(&mut self) {
+        while self.mag.last() == Some(&0) {
+            self.mag.pop();
+        }
+        if self.mag.is_empty() {
+            self.neg = false;
+        }
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Decimal) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Decimal) -> Ordering {
+        match (self.neg, other.neg) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (neg, _) => {
+                let scale = self.scale.max(other.scale);
+                let ord = cmp_mag(&self.rescaled(scale).mag, &other.rescaled(scale).mag);
+                if neg {
+                    ord.reverse()
+                } else {
+                    ord
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.neg {
+            write!(f, "-")?;
+        }
+        let precision = f.precision().unwrap_or(0).max(self.scale);
+        write!(f, "{}", self.to_fixed(precision))
+    }
+}
+
+// Compares two magnitudes.
+fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+// Adds b to a.
+fn add_mag(a: &mut Vec<u32>, b: &[u32]) {
+    if a.len() < b.len() {
+        a.resize(b.len(), 0);
+    }
+    let mut carry = 0;
+    for (i, limb) in a.iter_mut().enumerate() {
+        let sum = *limb + b.get(i).copied().unwrap_or(0) + carry;
+        *limb = sum % BASE;
+        carry = sum / BASE;
+        if carry == 0 && i >= b.len() {
+            break;
+        }
+    }
+    if carry > 0 {
+        a.push(carry);
+    }
+}
+
+// Subtracts b from a, which must be at least as great as b.
+fn sub_mag(a: &mut [u32], b: &[u32]) {
+    let mut borrow = 0;
+    for (i, limb) in a.iter_mut().enumerate() {
+        let sub = b.get(i).copied().unwrap_or(0) + borrow;
+        if *limb >= sub {
+            *limb -= sub;
+            borrow = 0;
+        } else {
+            *limb = *limb + BASE - sub;
+            borrow = 1;
+        }
+        if borrow == 0 && i >= b.len() {
+            break;
+        }
+    }
+}
+
+// Multiplies a by m.
+fn mul_small(a: &mut Vec<u32>, m: u64) {
+    let mut carry = 0u128;
+    for limb in a.iter_mut() {
+        let prod = *limb as u128 * m as u128 + carry;
+        *limb = (prod % BASE as u128) as u32;
+        carry = prod / BASE as u128;
+    }
+    while carry > 0 {
+        a.push((carry % BASE as u128) as u32);
+        carry /= BASE as u128;
+    }
+}
+
+#[path = "decimal_test.rs"]
+#[cfg(test)]
+mod test;
@@ -0,0 +1,133 @@
+use super::*;
+
+#[test]
+fn test_parse() {
+    for (num, exp, scale) in [
+        ("0", "0", 0),
+        ("42", "42", 0),
+        ("-42", "-42", 0),
+        ("+7", "7", 0),
+        (" 3 ", "3", 0),
+        (".5", "0.5", 1),
+        ("-.5", "-0.5", 1),
+        ("1.", "1", 0),
+        ("1.50", "1.50", 2),
+        ("-0", "0", 0),
+        ("-0.00", "0.00", 2),
+        ("1e3", "1000", 0),
+        ("1.5E2", "150", 0),
+        ("1.25e-3", "0.00125", 5),
+        ("-1e-3", "-0.001", 3),
+        ("0.000000001", "0.000000001", 9),
+        ("18446744073709551615", "18446744073709551615", 0),
+        (
+            "123456789012345678901234567890.123456789",
+            "123456789012345678901234567890.123456789",
+            9,
+        ),
+    ] {
+        let dec = Decimal::parse(num).unwrap_or_else(|| panic!("Should parse {}", num));
+        assert_eq!(dec.to_string(), exp, "Should format {}", num);
+        assert_eq!(dec.scale(), scale, "Should have scale of {}", num);
+    }
+}
+
+#[test]
+fn test_bad_parse() {
+    for num in [
+        "",
+        "-",
+        ".",
+        "-.",
+        "e3",
+        "1e",
+        "1e+",
+        "x",
+        "1x",
+        "1.2.3",
+        "--1",
+        "inf",
+        "-inf",
+        "nan",
+        "0x10",
+        "1_000",
+        "1e99999",
+        "1e-99999",
+        "1e9223372036854775807",
+        "1e-9223372036854775808",
+    ] {
+        assert!(Decimal::parse(num).is_none(), "Should not parse {}", num);
+    }
+}
+
+#[test]
+fn test_add() {
+    for (a, b, exp) in [
+        ("1", "1", "2"),
+        ("999999999", "1", "1000000000"),
+        ("18446744073709551615", "1", "18446744073709551616"),
+        ("1000000000", "-1", "999999999"),
+        ("1", "-2", "-1"),
+        ("-1", "2", "1"),
+        ("-1", "1", "0"),
+        ("-1", "-1", "-2"),
+        ("0.1", "0.2", "0.3"),
+        ("1", "0.25", "1.25"),
+        ("0.25", "1", "1.25"),
+        ("-0.000000001", "0.000000001", "0.000000000"),
+        ("1000000000000000000.5", "-0.5", "1000000000000000000.0"),
+    ] {
+        let mut dec = Decimal::parse(a).unwrap();
+        dec.add(&Decimal::parse(b).unwrap());
+        assert_eq!(dec.to_string(), exp, "Should get {} + {}", a, b);
+    }
+}
+
+#[test]
+fn test_cmp() {
+    for (a, b, exp) in [
+        ("1", "1", Ordering::Equal),
+        ("1", "1.000", Ordering::Equal),
+        ("-0", "0", Ordering::Equal),
+        ("1", "2", Ordering::Less),
+        ("2", "1", Ordering::Greater),
+        ("-1", "1", Ordering::Less),
+        ("-2", "-1", Ordering::Less),
+        ("0.1", "0.09", Ordering::Greater),
+        (
+            "18446744073709551620",
+            "18446744073709551610",
+            Ordering::Greater,
+        ),
+        ("1000000000", "999999999.999", Ordering::Greater),
+    ] {
+        assert_eq!(
+            Decimal::parse(a).unwrap().cmp(&Decimal::parse(b).unwrap()),
+            exp,
+            "Should compare {} and {}",
+            a,
+            b
+        );
+    }
+}
+
+#[test]
+fn test_to_fixed() {
+    for (num, precision, exp) in [
+        ("0", 0, "0"),
+        ("0", 2, "0.00"),
+        ("-5", 1, "5.0"),
+        ("0.5", 3, "0.500"),
+        ("0.001", 3, "0.001"),
+        ("1234567890", 2, "1234567890.00"),
+    ] {
+        assert_eq!(
+            Decimal::parse(num).unwrap().to_fixed(precision),
+            exp,
+            "Should format {} with precision {}",
+            num,
+            precision
+        );
+    }
+    assert_eq!(format!("{:.3}", Decimal::parse("-1.5").unwrap()), "-1.500");
+}
@@ -0,0 +1,115 @@
+use crate::crab_core::CrabError;
+use std::{error, fmt, io};
+
+// The name with which errors are prefixed.
+const NAME: &str = "seq";
+
+/// SeqError is an error from seq. Its message is formatted as the seq
+/// utility prints it, as in `seq: zero increment`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SeqError {
+    /// The command line is invalid: an unknown option, conflicting options,
+    /// or, without a message, the wrong number of operands.
+    Usage(Option<String>),
+    /// An operand is not a number.
+    InvalidNumber(String),
+    /// An operand is NaN, which cannot bound a sequence.
+    NotANumber(String),
+    /// An argument other than a number is invalid, such as a count, radix,
+    /// date, or ratio. The message explains why.
+    InvalidArgument(String),
+    /// A format is invalid. The message explains why.
+    InvalidFormat(String),
+    /// The increment is zero, or, if decrement is true, the decrement.
+    ZeroIncrement { decrement: bool },
+    /// The increment moves away from last: it must be negative if decrement
+    /// is true, and otherwise positive.
+    WrongDirection { decrement: bool },
+    /// The sequence has too many numbers to count.
+    TooLong,
+    /// Writing the sequence failed.
+    Io(io::Error),
+}
+
+impl SeqError {
+    // Returns the message of the error, without the name of the utility.
+    fn message(&self) -> String {
+        match self {
+            SeqError::Usage(None) => usage!().to_string(),
+            SeqError::Usage(Some(msg))
+            | SeqError::InvalidArgument(msg)
+            | SeqError::InvalidFormat(msg) => msg.clone(),
+            SeqError::InvalidNumber(arg) => format!("invalid floating point argument: {}", arg),
+            SeqError::NotANumber(arg) => format!("invalid not-a-number argument: {}", arg),
+            SeqError::ZeroIncrement { decrement } => format!(
+                "zero {}",
+                if *decrement { "decrement" } else { "increment" }
+            ),
+            SeqError::WrongDirection { decrement: true } => "needs negative decrement".into(),
+            SeqError::WrongDirection { decrement: false } => "needs positive increment".into(),
+            SeqError::TooLong => "sequence too long".into(),
+            SeqError::Io(err) => format!("write error: {}", err),
+        }
+    }
+
+    // Returns the shared error that displays the same message. I/O errors
+    // become failures, as an io::Error cannot be copied.
+    fn crab_error(&self) -> CrabError {
+        match self {
+            SeqError::Usage(message) => CrabError::Usage {
+                name: NAME,
+                message: message.clone(),
+                synopsis: usage!(),
+            },
+            err => CrabError::Failure {
+                name: NAME,
+                message: err.message(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for SeqError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.crab_error())
+    }
+}
+
+impl error::Error for SeqError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            SeqError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<getopts::Fail> for SeqError {
+    fn from(fail: getopts::Fail) -> SeqError {
+        SeqError::Usage(Some(fail.to_string()))
+    }
+}
+
+impl From<SeqError> for CrabError {
+    fn from(err: SeqError) -> CrabError {
+        match err {
+            SeqError::Io(err) => CrabError::Io {
+                name: NAME,
+                context: "write error",
+                err,
+            },
+            err => err.crab_error(),
+        }
+    }
+}
+
+impl From<io::Error> for SeqError {
+    fn from(err: io::Error) -> SeqError {
+        SeqError::Io(err)
+    }
+}
+
+#[path = "error_test.rs"]
+#[cfg(test)]
+mod test;
@@ -0,0 +1,84 @@
+use super::*;
+
+#[test]
+fn test_error() {
+    for (err, msg, code) in [
+        (SeqError::Usage(None), usage!(), 2),
+        (
+            SeqError::Usage(Some(
+                "--reverse may not be specified with --shuffle".to_string(),
+            )),
+            "seq: --reverse may not be specified with --shuffle",
+            2,
+        ),
+        (
+            SeqError::InvalidNumber("x".to_string()),
+            "seq: invalid floating point argument: x",
+            1,
+        ),
+        (
+            SeqError::NotANumber("nan".to_string()),
+            "seq: invalid not-a-number argument: nan",
+            1,
+        ),
+        (
+            SeqError::InvalidArgument("invalid radix: 1".to_string()),
+            "seq: invalid radix: 1",
+            1,
+        ),
+        (
+            SeqError::InvalidFormat("format '%' ends in %".to_string()),
+            "seq: format '%' ends in %",
+            1,
+        ),
+        (
+            SeqError::ZeroIncrement { decrement: false },
+            "seq: zero increment",
+            1,
+        ),
+        (
+            SeqError::ZeroIncrement { decrement: true },
+            "seq: zero decrement",
+            1,
+        ),
+        (
+            SeqError::WrongDirection { decrement: false },
+            "seq: needs positive increment",
+            1,
+        ),
+        (
+            SeqError::WrongDirection { decrement: true },
+            "seq: needs negative decrement",
+            1,
+        ),
+        (SeqError::TooLong, "seq: sequence too long", 1),
+        (
+            io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe").into(),
+            "seq: write error: broken pipe",
+            3,
+        ),
+    ] {
+        assert_eq!(err.to_string(), msg, "Should display {:?}", err);
+
+        // The shared error displays the same way.
+        let err = CrabError::from(err);
+        assert_eq!(err.to_string(), msg, "Should convert {:?}", err);
+        assert_eq!(err.exit_code(), code, "Should get exit code of {:?}", err);
+    }
+}
+
+#[test]
+fn test_is_broken_pipe() {
+    for (err, exp) in [
+        (io::Error::from(io::ErrorKind::BrokenPipe).into(), true),
+        (io::Error::from(io::ErrorKind::WriteZero).into(), false),
+        (SeqError::TooLong, false),
+    ] {
+        assert_eq!(
+            CrabError::from(err).is_broken_pipe(),
+            exp,
+            "Should check {:?}",
+            exp
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use super::{Result, SeqError};
+use std::{iter::Peekable, str::Chars};
+
+/// Interprets the backslash escapes of a separator or terminator string:
+/// `\a`, `\b`, `\f`, `\n`, `\r`, `\t`, `\v`, `\\`, `\0`, up to three octal
+/// digits `\NNN`, up to two hexadecimal digits `\xHH`, and a Unicode scalar
+/// value `\u{HHHHHH}`. Octal and hexadecimal escapes denote ASCII characters
+/// only. A backslash followed by any other character is kept as it is.
+pub fn unescape(s: &str) -> Result<String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        let escaped = match chars.peek() {
+            Some('a') => '\x07',
+            Some('b') => '\x08',
+            Some('f') => '\x0c',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('v') => '\x0b',
+            Some('\\') => '\\',
+            Some('0'..='7') => {
+                out.push(ascii(s, digits(&mut chars, 8, 3))?);
+                continue;
+            }
+            Some('x') => {
+                chars.next();
+                match digits(&mut chars, 16, 2) {
+                    Some(n) => out.push(ascii(s, Some(n))?),
+                    None => out.push_str("\\x"),
+                }
+                continue;
+            }
+            Some('u') => {
+                chars.next();
+                out.push(unicode(s, &mut chars)?);
+                continue;
+            }
+            _ => {
+                out.push('\\');
+                continue;
+            }
+        };
+        chars.next();
+        out.push(escaped);
+    }
+    Ok(out)
+}
+
+// Parses up to max digits in a radix.
+fn digits(chars: &mut Peekable<Chars>, radix: u32, max: usize) -> Option<u32> {
+    let mut num = None;
+    for _ in 0..max {
+        match chars.peek().and_then(|c| c.to_digit(radix)) {
+            Some(d) => num = Some(num.unwrap_or(0) * radix + d),
+            None => break,
+        }
+        chars.next();
+    }
+    num
+}
+
+// Returns the ASCII character with a code parsed from an escape.
+fn ascii(s: &str, code: Option<u32>) -> Result<char> {
+    match code {
+        Some(n) if n < 0x80 => Ok(n as u8 as char),
+        _ => Err(invalid(s)),
+    }
+}
+
+// Parses the braced hexadecimal digits of a \u{...} escape.
+fn unicode(s: &str, chars: &mut Peekable<Chars>) -> Result<char> {
+    if chars.next() != Some('{') {
+        return Err(invalid(s));
+    }
+    let code = digits(chars, 16, 6);
+    if chars.next() != Some('}') {
+        return Err(invalid(s));
+    }
+    code.and_then(char::from_u32).ok_or_else(|| invalid(s))
+}
+
+fn invalid(s: &str) -> SeqError {
+    SeqError::InvalidArgument(format!("invalid escape sequence in '{}'", s))
+}
+
+#[path = "escape_test.rs"]
+#[cfg(test)]
+mod test;
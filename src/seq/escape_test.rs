@@ -0,0 +1,58 @@
+use super::*;
+
+#[test]
+fn test_unescape() -> Result<()> {
+    for (s, exp) in [
+        ("", ""),
+        (",", ","),
+        ("\\n", "\n"),
+        ("\\t", "\t"),
+        ("a\\tb\\r\\n", "a\tb\r\n"),
+        ("\\a\\b\\f\\v", "\x07\x08\x0c\x0b"),
+        ("\\\\", "\\"),
+        ("\\\\n", "\\n"),
+        ("\\0", "\0"),
+        ("\\0x", "\0x"),
+        ("\\101", "A"),
+        ("\\1011", "A1"),
+        ("\\18", "\x018"),
+        ("\\x41", "A"),
+        ("\\x9", "\t"),
+        ("\\x7fF", "\x7fF"),
+        ("\\x", "\\x"),
+        ("\\xg", "\\xg"),
+        ("\\u{41}", "A"),
+        ("\\u{1F980}", "🦀"),
+        ("\\u{0}", "\0"),
+        ("\\q", "\\q"),
+        ("\\", "\\"),
+        ("🦀\\n", "🦀\n"),
+    ] {
+        assert_eq!(unescape(s)?, exp, "Should unescape {:?}", s);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_bad_unescape() {
+    for s in [
+        "\\x80",
+        "\\377",
+        "\\u41",
+        "\\u{41",
+        "\\u{}",
+        "\\u{1234567}",
+        "\\u{D800}",
+        "\\u{110000}",
+    ] {
+        match unescape(s) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                format!("seq: invalid escape sequence in '{}'", s),
+                "Should get error for {:?}",
+                s
+            ),
+            Ok(x) => panic!("Should get error for {:?}, got {:?}", s, x),
+        }
+    }
+}
@@ -0,0 +1,467 @@
+use super::{decimal::Decimal, numeric::Numeric, Result, SeqError};
+
+/// The greatest width to which a format pads numbers.
+pub const MAX_WIDTH: usize = u16::MAX as usize;
+
+/// The greatest precision a format accepts.
+pub const MAX_PRECISION: usize = u16::MAX as usize;
+
+// Flags that may appear between the % and the width of a directive.
+#[derive(Debug, Default, PartialEq)]
+struct Flags {
+    left: bool,
+    plus: bool,
+    space: bool,
+    alt: bool,
+    zero: bool,
+    group: bool,
+}
+
+/// Format is a parsed printf-style format string containing exactly one
+/// floating point conversion, as accepted by `seq -f`.
+#[derive(Debug, PartialEq)]
+pub struct Format {
+    prefix: String,
+    flags: Flags,
+    width: usize,
+    precision: Option<usize>,
+    conv: char,
+    suffix: String,
+    numeric: Numeric,
+}
+
+impl Format {
+    /// Creates the default format, equivalent to `%0{width}.{precision}f`.
+    pub fn fixed(width: usize, precision: usize) -> Format {
+        Format {
+            prefix: String::new(),
+            flags: Flags {
+                zero: true,
+                ..Flags::default()
+            },
+            width,
+            precision: Some(precision),
+            conv: 'f',
+            suffix: String::new(),
+            numeric: Numeric::default(),
+        }
+    }
+
+    /// Creates a format for integers in base 2, 8, or 16, zero-padded to the
+    /// width. If prefix is true, the digits are preceded by `0b`, `0o`, or
+    /// `0x`. Render exact integers with [`Format::render_int`].
+    pub fn radix(radix: u32, width: usize, prefix: bool) -> Format {
+        Format {
+            prefix: String::new(),
+            flags: Flags {
+                zero: true,
+                alt: prefix,
+                ..Flags::default()
+            },
+            width,
+            precision: None,
+            conv: match radix {
+                2 => 'b',
+                8 => 'o',
+                _ => 'x',
+            },
+            suffix: String::new(),
+            numeric: Numeric::default(),
+        }
+    }
+
+    /// Parses a printf-style format. The format must contain exactly one
+    /// `a`, `A`, `e`, `E`, `f`, `F`, `g` or `G` conversion, optionally
+    /// preceded by flags, a width of at most [`MAX_WIDTH`], a precision of
+    /// at most [`MAX_PRECISION`], and an `L` length modifier, which is
+    /// ignored. The `'` flag requests grouped digits, which
+    /// [`Format::numeric`] supplies. Use `%%` for a literal percent sign.
+    pub fn parse(fmt: &str) -> Result<Format> {
+        let mut chars = fmt.chars().peekable();
+        let mut prefix = String::new();
+
+        // Collect the prefix up to the directive.
+        loop {
+            match chars.next() {
+                None => {
+                    return Err(SeqError::InvalidFormat(format!(
+                        "format '{}' has no % directive",
+                        fmt
+                    )))
+                }
+                Some('%') => {
+                    if chars.peek() == Some(&'%') {
+                        chars.next();
+                        prefix.push('%');
+                    } else {
+                        break;
+                    }
+                }
+                Some(c) => prefix.push(c),
+            }
+        }
+
+        // Parse the flags.
+        let mut flags = Flags::default();
+        while let Some(c) = chars.peek() {
+            match c {
+                '-' => flags.left = true,
+                '+' => flags.plus = true,
+                ' ' => flags.space = true,
+                '#' => flags.alt = true,
+                '0' => flags.zero = true,
+                '\'' => flags.group = true,
+                _ => break,
+            }
+            chars.next();
+        }
+
+        // Parse the width and precision.
+        let width = digits(&mut chars).unwrap_or(0);
+        let precision = if chars.peek() == Some(&'.') {
+            chars.next();
+            Some(digits(&mut chars).unwrap_or(0))
+        } else {
+            None
+        };
+        if width > MAX_WIDTH {
+            return Err(SeqError::InvalidFormat(format!(
+                "format '{}' has a width greater than {}",
+                fmt, MAX_WIDTH
+            )));
+        }
+        if precision.is_some_and(|p| p > MAX_PRECISION) {
+            return Err(SeqError::InvalidFormat(format!(
+                "format '{}' has a precision greater than {}",
+                fmt, MAX_PRECISION
+            )));
+        }
+
+        // Every number is a double, so the long double modifier means nothing.
+        if chars.peek() == Some(&'L') {
+            chars.next();
+        }
+
+        let conv = match chars.next() {
+            Some(c @ ('a' | 'A' | 'e' | 'E' | 'f' | 'F' | 'g' | 'G')) => c,
+            Some(c) => {
+                return Err(SeqError::InvalidFormat(format!(
+                    "format '{}' has unknown %{} directive",
+                    fmt, c
+                )))
+            }
+            None => {
+                return Err(SeqError::InvalidFormat(format!(
+                    "format '{}' ends in %",
+                    fmt
+                )))
+            }
+        };
+
+        // Collect the suffix.
+        let mut suffix = String::new();
+        while let Some(c) = chars.next() {
+            if c == '%' && chars.next() != Some('%') {
+                return Err(SeqError::InvalidFormat(format!(
+                    "format '{}' has too many % directives",
+                    fmt
+                )));
+            }
+            suffix.push(c);
+        }
+
+        Ok(Format {
+            prefix,
+            flags,
+            width,
+            precision,
+            conv,
+            suffix,
+            numeric: Numeric::default(),
+        })
+    }
+
+    /// Returns the format padded to its width with zeros if zero is true,
+    /// and otherwise with spaces.
+    pub fn zero(self, zero: bool) -> Format {
+        Format {
+            flags: Flags { zero, ..self.flags },
+            ..self
+        }
+    }
+
+    /// Returns the format with decimal numbers written in the conventions
+    /// of numeric. Hexadecimal floating point and radix conversions are
+    /// unaffected.
+    pub fn numeric(self, numeric: &Numeric) -> Format {
+        Format {
+            numeric: numeric.clone(),
+            ..self
+        }
+    }
+
+    /// Reports whether the format has the `'` flag, requesting digits
+    /// grouped by the thousands separator.
+    pub fn grouped(&self) -> bool {
+        self.flags.group
+    }
+
+    /// Formats x according to the format.
+    pub fn render(&self, x: f64) -> String {
+        let upper = self.conv.is_ascii_uppercase();
+        let (radix, mut body) = if x.is_finite() {
+            match self.conv.to_ascii_lowercase() {
+                'a' => ("0x", hexfloat(x.abs(), self.precision, self.flags.alt)),
+                'b' => (self.alt("0b"), format!("{:b}", x.abs() as u128)),
+                'o' => (self.alt("0o"), format!("{:o}", x.abs() as u128)),
+                'x' => (self.alt("0x"), format!("{:x}", x.abs() as u128)),
+                'e' => (
+                    "",
+                    exponential(x.abs(), self.precision.unwrap_or(6), self.flags.alt),
+                ),
+                'g' => (
+                    "",
+                    general(x.abs(), self.precision.unwrap_or(6), self.flags.alt),
+                ),
+                _ => (
+                    "",
+                    fixed(x.abs(), self.precision.unwrap_or(6), self.flags.alt),
+                ),
+            }
+        } else if x.is_nan() {
+            ("", "nan".to_string())
+        } else {
+            ("", "inf".to_string())
+        };
+        if upper {
+            body = body.to_ascii_uppercase();
+        }
+        let radix = if upper {
+            radix.to_ascii_uppercase()
+        } else {
+            radix.to_string()
+        };
+
+        self.pad(
+            x.is_sign_negative() && !x.is_nan(),
+            &radix,
+            &body,
+            x.is_finite(),
+        )
+    }
+
+    /// Formats an exact decimal according to the format, which must be a
+    /// `%f` conversion with a precision no less than the decimal's scale.
+    pub fn render_decimal(&self, x: &Decimal) -> String {
+        let precision = self.precision.unwrap_or(6);
+        let mut body = x.to_fixed(precision);
+        if self.flags.alt && precision == 0 {
+            body.push('.');
+        }
+        self.pad(x.is_negative(), "", &body, true)
+    }
+
+    /// Formats an integer according to the format, which must be a radix
+    /// conversion created by [`Format::radix`].
+    pub fn render_int(&self, x: i128) -> String {
+        let n = x.unsigned_abs();
+        let (radix, body) = match self.conv {
+            'b' => (self.alt("0b"), format!("{:b}", n)),
+            'o' => (self.alt("0o"), format!("{:o}", n)),
+            _ => (self.alt("0x"), format!("{:x}", n)),
+        };
+        self.pad(x < 0, radix, &body, true)
+    }
+
+    /// Reports whether the format is a radix conversion.
+    pub fn is_radix(&self) -> bool {
+        matches!(self.conv, 'b' | 'o' | 'x')
+    }
+
+    // Returns the radix prefix if the alternate form flag is set.
+    fn alt(&self, prefix: &'static str) -> &'static str {
+        if self.flags.alt {
+            prefix
+        } else {
+            ""
+        }
+    }
+
+    // Adds the sign, radix prefix, padding, and affixes to a formatted number.
+    fn pad(&self, neg: bool, radix: &str, body: &str, zero: bool) -> String {
+        let sign = if neg {
+            "-"
+        } else if self.flags.plus {
+            "+"
+        } else if self.flags.space {
+            " "
+        } else {
+            ""
+        };
+
+        // Write decimal numbers in the numeric conventions, grouping any zero
+        // padding with the digits.
+        let body = match self.conv {
+            'a' | 'A' | 'b' | 'o' | 'x' => body.to_string(),
+            _ if self.numeric == Numeric::default() => body.to_string(),
+            _ if self.flags.zero && zero && !self.flags.left => self
+                .numeric
+                .apply(body, self.width.saturating_sub(sign.len() + radix.len())),
+            _ => self.numeric.apply(body, 0),
+        };
+        let body = body.as_str();
+        let len = sign.len() + radix.len() + body.chars().count();
+        let pad = self.width.saturating_sub(len);
+        let mut num = String::with_capacity(len + pad);
+        if self.flags.left {
+            num.push_str(sign);
+            num.push_str(radix);
+            num.push_str(body);
+            num.push_str(&" ".repeat(pad));
+        } else if self.flags.zero && zero {
+            num.push_str(sign);
+            num.push_str(radix);
+            num.push_str(&"0".repeat(pad));
+            num.push_str(body);
+        } else {
+            num.push_str(&" ".repeat(pad));
+            num.push_str(sign);
+            num.push_str(radix);
+            num.push_str(body);
+        }
+
+        format!("{}{}{}", self.prefix, num, self.suffix)
+    }
+}
+
+// Parses a run of ASCII digits.
+fn digits(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<usize> {
+    let mut num: Option<usize> = None;
+    while let Some(d) = chars.peek().and_then(|c| c.to_digit(10)) {
+        num = Some(
+            num.unwrap_or(0)
+                .saturating_mul(10)
+                .saturating_add(d as usize),
+        );
+        chars.next();
+    }
+    num
+}
+
+// Formats a non-negative finite x like %f. Rust writes at most
+// MAX_PRECISION places, past which those of an f64 are all zeros anyway.
+fn fixed(x: f64, precision: usize, alt: bool) -> String {
+    let mut s = format!("{:.*}", precision.min(MAX_PRECISION), x);
+    s.push_str(&"0".repeat(precision.saturating_sub(MAX_PRECISION)));
+    if alt && precision == 0 {
+        s.push('.');
+    }
+    s
+}
+
+// Formats a non-negative finite x like %e.
+fn exponential(x: f64, precision: usize, alt: bool) -> String {
+    // Rust formats the exponent as e.g. "e3" or "e-3"; printf wants at least
+    // two digits and an explicit sign. Rust also writes at most MAX_PRECISION
+    // significant digits, so pad any further places with zeros as in fixed.
+    let s = format!("{:.*e}", precision.min(MAX_PRECISION - 1), x);
+    let (mantissa, exp) = s.split_at(s.find('e').unwrap());
+    let exp: i32 = exp[1..].parse().unwrap();
+    format!(
+        "{}{}{}e{}{:02}",
+        mantissa,
+        "0".repeat(precision.saturating_sub(MAX_PRECISION - 1)),
+        if alt && precision == 0 { "." } else { "" },
+        if exp < 0 { '-' } else { '+' },
+        exp.abs()
+    )
+}
+
+// Formats a non-negative finite x like %g.
+fn general(x: f64, precision: usize, alt: bool) -> String {
+    let p = precision.max(1);
+
+    // Determine the exponent after rounding to p significant digits.
+    let e = format!("{:.*e}", p - 1, x);
+    let exp: i32 = e[e.find('e').unwrap() + 1..].parse().unwrap();
+
+    let s = if exp < -4 || exp >= p as i32 {
+        exponential(x, p - 1, alt)
+    } else {
+        fixed(x, (p as i32 - 1 - exp) as usize, alt)
+    };
+    if alt {
+        return s;
+    }
+
+    // Strip trailing zeros from the fraction.
+    match s.find('e') {
+        Some(idx) => trim_fraction(&s[..idx]) + &s[idx..],
+        None => trim_fraction(&s),
+    }
+}
+
+// Removes trailing zeros and a trailing decimal point from a number.
+fn trim_fraction(s: &str) -> String {
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+// Formats a non-negative finite x like %a, without the leading 0x.
+fn hexfloat(x: f64, precision: Option<usize>, alt: bool) -> String {
+    if x == 0.0 {
+        let frac = "0".repeat(precision.unwrap_or(0));
+        let point = if !frac.is_empty() || alt { "." } else { "" };
+        return format!("0{}{}p+0", point, frac);
+    }
+
+    // Normalize the mantissa to 1.xxx, including subnormals.
+    let bits = x.to_bits();
+    let mut exp = ((bits >> 52) & 0x7ff) as i32;
+    let mut mant = bits & ((1 << 52) - 1);
+    if exp == 0 {
+        let shift = mant.leading_zeros() - 11;
+        mant = (mant << shift) & ((1 << 52) - 1);
+        exp = 1 - shift as i32;
+    }
+    exp -= 1023;
+    let mut lead = 1u64;
+
+    let frac = match precision {
+        Some(p) if p < 13 => {
+            // Round half to even at the requested number of hex digits.
+            let drop = 4 * (13 - p) as u32;
+            let full = (lead << 52) | mant;
+            let half = 1u64 << (drop - 1);
+            let rem = full & ((1u64 << drop) - 1);
+            let mut kept = full >> drop;
+            if rem > half || (rem == half && kept & 1 == 1) {
+                kept += 1;
+            }
+            lead = kept >> (4 * p);
+            if p == 0 {
+                String::new()
+            } else {
+                format!("{:01$x}", kept & ((1u64 << (4 * p)) - 1), p)
+            }
+        }
+        Some(p) => format!("{:013x}", mant) + &"0".repeat(p - 13),
+        None => format!("{:013x}", mant).trim_end_matches('0').to_string(),
+    };
+
+    format!(
+        "{}{}{}p{}{}",
+        lead,
+        if !frac.is_empty() || alt { "." } else { "" },
+        frac,
+        if exp < 0 { '-' } else { '+' },
+        exp.abs()
+    )
+}
+
+#[path = "format_test.rs"]
+#[cfg(test)]
+mod test;
@@ -0,0 +1,326 @@
+use super::*;
+
+#[test]
+fn test_parse() {
+    struct TestCase<'a> {
+        desc: &'a str,
+        fmt: &'a str,
+        exp: Format,
+    }
+
+    for item in [
+        TestCase {
+            desc: "plain g",
+            fmt: "%g",
+            exp: Format {
+                prefix: "".into(),
+                flags: Flags::default(),
+                width: 0,
+                precision: None,
+                conv: 'g',
+                suffix: "".into(),
+                numeric: Numeric::default(),
+            },
+        },
+        TestCase {
+            desc: "width and precision",
+            fmt: "%8.3f",
+            exp: Format {
+                prefix: "".into(),
+                flags: Flags::default(),
+                width: 8,
+                precision: Some(3),
+                conv: 'f',
+                suffix: "".into(),
+                numeric: Numeric::default(),
+            },
+        },
+        TestCase {
+            desc: "empty precision",
+            fmt: "%.E",
+            exp: Format {
+                prefix: "".into(),
+                flags: Flags::default(),
+                width: 0,
+                precision: Some(0),
+                conv: 'E',
+                suffix: "".into(),
+                numeric: Numeric::default(),
+            },
+        },
+        TestCase {
+            desc: "all flags",
+            fmt: "%-+ #0'a",
+            exp: Format {
+                prefix: "".into(),
+                flags: Flags {
+                    left: true,
+                    plus: true,
+                    space: true,
+                    alt: true,
+                    zero: true,
+                    group: true,
+                },
+                width: 0,
+                precision: None,
+                conv: 'a',
+                suffix: "".into(),
+                numeric: Numeric::default(),
+            },
+        },
+        TestCase {
+            desc: "prefix and suffix",
+            fmt: "file%%-%03g.txt%%",
+            exp: Format {
+                prefix: "file%-".into(),
+                flags: Flags {
+                    zero: true,
+                    ..Flags::default()
+                },
+                width: 3,
+                precision: None,
+                conv: 'g',
+                suffix: ".txt%".into(),
+                numeric: Numeric::default(),
+            },
+        },
+        TestCase {
+            desc: "fixed",
+            fmt: "%05.2f",
+            exp: Format::fixed(5, 2),
+        },
+        TestCase {
+            desc: "long double",
+            fmt: "%8.3Lf",
+            exp: Format {
+                prefix: "".into(),
+                flags: Flags::default(),
+                width: 8,
+                precision: Some(3),
+                conv: 'f',
+                suffix: "".into(),
+                numeric: Numeric::default(),
+            },
+        },
+    ] {
+        assert_eq!(
+            Format::parse(item.fmt).unwrap(),
+            item.exp,
+            "Should parse {}",
+            item.desc
+        );
+    }
+}
+
+#[test]
+fn test_bad_parse() {
+    for (fmt, err) in [
+        ("", "seq: format '' has no % directive"),
+        ("foo", "seq: format 'foo' has no % directive"),
+        ("100%%", "seq: format '100%%' has no % directive"),
+        ("%d", "seq: format '%d' has unknown %d directive"),
+        ("%5.2s", "seq: format '%5.2s' has unknown %s directive"),
+        ("%g%g", "seq: format '%g%g' has too many % directives"),
+        ("%f %", "seq: format '%f %' has too many % directives"),
+        ("x%", "seq: format 'x%' ends in %"),
+        ("%-5", "seq: format '%-5' ends in %"),
+        ("%Ld", "seq: format '%Ld' has unknown %d directive"),
+        ("%lf", "seq: format '%lf' has unknown %l directive"),
+        (
+            "%65536f",
+            "seq: format '%65536f' has a width greater than 65535",
+        ),
+        (
+            "%.65536e",
+            "seq: format '%.65536e' has a precision greater than 65535",
+        ),
+    ] {
+        match Format::parse(fmt) {
+            Err(e) => assert_eq!(e.to_string(), err, "Should get error for {}", fmt),
+            Ok(_) => panic!("Should get error for {}", fmt),
+        }
+    }
+}
+
+#[test]
+fn test_render() {
+    for (fmt, num, exp) in [
+        // Fixed.
+        ("%f", 1.0, "1.000000"),
+        ("%.2f", 1.23456, "1.23"),
+        ("%.0f", 2.5, "2"),
+        ("%#.0f", 2.0, "2."),
+        ("%F", f64::INFINITY, "INF"),
+        ("%f", -f64::INFINITY, "-inf"),
+        ("%f", f64::NAN, "nan"),
+        // Exponential.
+        ("%e", 1234.5, "1.234500e+03"),
+        ("%.2e", 0.000123, "1.23e-04"),
+        ("%E", 1e100, "1.000000E+100"),
+        ("%.0e", 5.0, "5e+00"),
+        ("%#.0e", 5.0, "5.e+00"),
+        // General.
+        ("%g", 9.0, "9"),
+        ("%g", 9.25, "9.25"),
+        ("%g", 100000.0, "100000"),
+        ("%g", 1000000.0, "1e+06"),
+        ("%g", 0.0001, "0.0001"),
+        ("%g", 0.00001, "1e-05"),
+        ("%.3g", 1.23456, "1.23"),
+        ("%.0g", 7.0, "7"),
+        ("%#g", 9.5, "9.50000"),
+        ("%G", 1.5e-10, "1.5E-10"),
+        ("%g", 0.0, "0"),
+        // Hexadecimal.
+        ("%a", 1.0, "0x1p+0"),
+        ("%a", 0.5, "0x1p-1"),
+        ("%a", 10.0, "0x1.4p+3"),
+        ("%A", -1.0, "-0X1P+0"),
+        ("%.2a", 1.0, "0x1.00p+0"),
+        ("%.1a", 1.96875, "0x2.0p+0"),
+        ("%a", 0.0, "0x0p+0"),
+        ("%a", 5e-324, "0x1p-1074"),
+        // Flags, widths and affixes.
+        ("%+g", 1.0, "+1"),
+        ("% g", 1.0, " 1"),
+        ("%+ g", -1.0, "-1"),
+        ("%5g", 1.0, "    1"),
+        ("%-5g|", 1.0, "1    |"),
+        ("%05g", -1.0, "-0001"),
+        ("%-05g", 1.0, "1    "),
+        ("%05f", f64::INFINITY, "  inf"),
+        ("%08a", 1.0, "0x001p+0"),
+        ("%05.1f", 9.5, "009.5"),
+        ("%05.1f", -9.5, "-09.5"),
+        ("n=%g%%", 3.0, "n=3%"),
+    ] {
+        assert_eq!(
+            Format::parse(fmt).unwrap().render(num),
+            exp,
+            "Should render {} with {}",
+            num,
+            fmt
+        );
+    }
+}
+
+#[test]
+fn test_render_max_precision() {
+    for (fmt, num, len) in [
+        ("%.65535f", 0.5, 65537),
+        ("%.65535e", 0.5, 65541),
+        ("%#.65535g", 0.001, 65539),
+    ] {
+        assert_eq!(
+            Format::parse(fmt).unwrap().render(num).len(),
+            len,
+            "Should render {} with {}",
+            num,
+            fmt
+        );
+    }
+}
+
+#[test]
+fn test_render_numeric() {
+    let en = Numeric::default().group(",");
+    let de = Numeric::default().group(".").point(',');
+    for (fmt, numeric, num, exp) in [
+        ("%.2f", &en, 1234567.5, "1,234,567.50"),
+        ("%.1f", &de, -1234.5, "-1.234,5"),
+        ("%g", &en, 1e6, "1e+06"),
+        ("%09.1f", &en, 1234.5, "001,234.5"),
+        ("%09.1f", &en, -1234.5, "-01,234.5"),
+        ("%9.1f", &en, 1234.5, "  1,234.5"),
+        ("%-9.1f|", &en, 1234.5, "1,234.5  |"),
+        ("%a", &de, 1.5, "0x1.8p+0"),
+        ("%f", &en, f64::INFINITY, "inf"),
+    ] {
+        assert_eq!(
+            Format::parse(fmt).unwrap().numeric(numeric).render(num),
+            exp,
+            "Should render {} with {} in {:?}",
+            num,
+            fmt,
+            numeric
+        );
+    }
+    assert_eq!(
+        Format::radix(16, 1, true).numeric(&en).render(4096.0),
+        "0x1000"
+    );
+    assert_eq!(
+        Format::fixed(7, 1)
+            .numeric(&de)
+            .render_decimal(&Decimal::parse("1234.5").unwrap()),
+        "1.234,5"
+    );
+}
+
+#[test]
+fn test_zero() {
+    for (fmt, zero, num, exp) in [
+        (Format::fixed(4, 0), true, -5.0, "-005"),
+        (Format::fixed(4, 0), false, -5.0, "  -5"),
+        (Format::fixed(5, 1), false, 2.5, "  2.5"),
+        (Format::radix(16, 5, true), false, 15.0, "  0xf"),
+        (Format::parse("%5g").unwrap(), true, -1.0, "-0001"),
+    ] {
+        assert_eq!(
+            fmt.zero(zero).render(num),
+            exp,
+            "Should pad {} with zeros: {}",
+            num,
+            zero
+        );
+    }
+}
+
+#[test]
+fn test_radix() {
+    for (radix, width, prefix, num, exp) in [
+        (16, 1, false, 255.0, "ff"),
+        (16, 1, true, 255.0, "0xff"),
+        (16, 4, false, 255.0, "00ff"),
+        (16, 6, true, 255.0, "0x00ff"),
+        (16, 6, true, -255.0, "-0x0ff"),
+        (16, 1, false, 0.0, "0"),
+        (8, 1, false, 8.0, "10"),
+        (8, 1, true, 8.0, "0o10"),
+        (8, 3, false, -8.0, "-10"),
+        (2, 1, false, 5.0, "101"),
+        (2, 8, true, 5.0, "0b000101"),
+        (16, 1, false, 18446744073709551616.0, "10000000000000000"),
+        (16, 1, true, f64::INFINITY, "inf"),
+    ] {
+        assert_eq!(
+            Format::radix(radix, width, prefix).render(num),
+            exp,
+            "Should render {} in base {}",
+            num,
+            radix
+        );
+    }
+}
+
+#[test]
+fn test_render_int() {
+    for (radix, width, prefix, num, exp) in [
+        (16, 1, false, 255, "ff"),
+        (16, 6, true, -255, "-0x0ff"),
+        (16, 1, false, 0, "0"),
+        (8, 3, false, -8, "-10"),
+        (2, 8, true, 5, "0b000101"),
+        (16, 1, false, 9007199254740993, "20000000000001"),
+        (16, 1, true, 18446744073709551615, "0xffffffffffffffff"),
+        (16, 1, false, i128::MIN, "-80000000000000000000000000000000"),
+    ] {
+        assert_eq!(
+            Format::radix(radix, width, prefix).render_int(num),
+            exp,
+            "Should render {} in base {}",
+            num,
+            radix
+        );
+    }
+}
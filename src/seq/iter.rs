@@ -0,0 +1,255 @@
+use super::{floatat, floatcount, format::Format, increment, places, Result, SeqError};
+use std::{cmp, convert::TryFrom, fmt, iter::FusedIterator};
+
+/// SeqSpec specifies a sequence from first to last in increments of incr,
+/// displayed with precision decimal places. If decimal is true, the operands
+/// are decimal numbers, and items within floating point error of a number at
+/// the precision are rounded to it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SeqSpec<T = f64> {
+    pub first: T,
+    pub incr: T,
+    pub last: T,
+    pub precision: usize,
+    pub decimal: bool,
+}
+
+impl<T> SeqSpec<T> {
+    /// Creates a specification with a precision of zero, whose items are not
+    /// rounded.
+    pub fn new(first: T, incr: T, last: T) -> SeqSpec<T> {
+        SeqSpec {
+            first,
+            incr,
+            last,
+            precision: 0,
+            decimal: false,
+        }
+    }
+}
+
+/// Seq builds a sequence of numbers from first to last in increments of
+/// incr, as printed by `seq first incr last`.
+///
+/// Seq works in f64 throughout. Unlike the seq utility, which counts decimal
+/// and integer operands exactly and takes its default precision from their
+/// text, Seq takes the default precision from the values of the operands, to
+/// at most 15 significant digits.
+///
+/// ```
+/// use seq::Seq;
+///
+/// let nums: Vec<f64> = Seq::new(3.0, 1.0).iter()?.collect();
+/// assert_eq!(nums, [3.0, 2.0, 1.0]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Seq {
+    first: f64,
+    incr: Option<f64>,
+    last: f64,
+    precision: Option<usize>,
+    equal_width: bool,
+    format: Option<String>,
+    separator: String,
+    terminator: Option<String>,
+}
+
+impl Seq {
+    /// Creates a sequence from first to last, in increments of 1, or of -1
+    /// if first is greater than last.
+    pub fn new(first: f64, last: f64) -> Seq {
+        Seq {
+            first,
+            incr: None,
+            last,
+            precision: None,
+            equal_width: false,
+            format: None,
+            separator: "\n".to_string(),
+            terminator: None,
+        }
+    }
+
+    /// Sets the increment, which must be positive if first is less than
+    /// last, and negative if first is greater than last.
+    pub fn incr(mut self, incr: f64) -> Seq {
+        self.incr = Some(incr);
+        self
+    }
+
+    /// Sets the number of decimal places to display. The default is the
+    /// greatest of first, incr, and last, to at most 15 significant digits.
+    pub fn precision(mut self, precision: usize) -> Seq {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Sets whether to display numbers at equal widths by padding them with
+    /// zeros, like `seq -w`.
+    pub fn equal_width(mut self, equal_width: bool) -> Seq {
+        self.equal_width = equal_width;
+        self
+    }
+
+    /// Sets a printf-style format to display each number, like `seq -f`.
+    pub fn format(mut self, format: &str) -> Seq {
+        self.format = Some(format.to_string());
+        self
+    }
+
+    /// Sets the string to display after each number, like `seq -s`. The
+    /// default is a newline.
+    pub fn separator(mut self, separator: &str) -> Seq {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Sets the string to display after the sequence, like `seq -t`.
+    pub fn terminator(mut self, terminator: &str) -> Seq {
+        self.terminator = Some(terminator.to_string());
+        self
+    }
+
+    /// Returns an iterator over the numbers of the sequence. Fails if an
+    /// operand is NaN, if the increment is zero or runs away from last, or
+    /// if the sequence has more than `usize::MAX` numbers.
+    pub fn iter(&self) -> Result<SeqIter> {
+        // Count at the precision of the operands rather than that set for
+        // display, which may be too coarse to tell where the sequence ends.
+        let s = self.operands()?;
+        let len = floatcount(&s)?;
+        if usize::try_from(len).is_err() {
+            return Err(SeqError::TooLong);
+        }
+        Ok(SeqIter {
+            spec: s,
+            front: 0,
+            back: len,
+        })
+    }
+
+    /// Returns a value that displays the sequence as the seq utility prints
+    /// it.
+    ///
+    /// ```
+    /// use seq::Seq;
+    ///
+    /// let text = Seq::new(8.0, 10.0).equal_width(true).display()?;
+    /// assert_eq!(text.to_string(), "08\n09\n10\n");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn display(&self) -> Result<SeqDisplay> {
+        let s = self.spec()?;
+        let fmt = match &self.format {
+            Some(_) if self.equal_width => {
+                return Err(SeqError::Usage(Some(
+                    "format string may not be specified when printing equal width strings"
+                        .to_string(),
+                )))
+            }
+            Some(f) => Format::parse(f)?,
+            None if self.equal_width => Format::fixed(width!(s), s.precision),
+            None => Format::fixed(1, s.precision),
+        };
+        Ok(SeqDisplay {
+            iter: self.iter()?,
+            fmt,
+            separator: self.separator.clone(),
+            terminator: self.terminator.clone(),
+        })
+    }
+
+    /// Returns the specification of the sequence, with the default increment
+    /// and precision filled in. Fails if an operand is NaN, or if the
+    /// increment is zero or runs away from last.
+    pub fn spec(&self) -> Result<SeqSpec> {
+        let s = self.operands()?;
+        Ok(SeqSpec {
+            precision: self.precision.unwrap_or(s.precision),
+            ..s
+        })
+    }
+
+    // Returns the specification of the sequence at the precision of its
+    // operands.
+    fn operands(&self) -> Result<SeqSpec> {
+        let (first, last) = (self.first, self.last);
+        let incr = increment(first, self.incr, last)?;
+        let precision = cmp::max(places(first), cmp::max(places(incr), places(last)));
+        Ok(SeqSpec {
+            first,
+            incr,
+            last,
+            precision,
+            decimal: true,
+        })
+    }
+}
+
+/// SeqIter iterates over the numbers of a [`Seq`], from either end.
+#[derive(Clone, Debug)]
+pub struct SeqIter {
+    spec: SeqSpec,
+    // The indexes of the next numbers from the front and after the back.
+    front: u64,
+    back: u64,
+}
+
+impl Iterator for SeqIter {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(floatat(&self.spec, self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Seq::iter ensures that the length fits in a usize.
+        let n = (self.back - self.front) as usize;
+        (n, Some(n))
+    }
+}
+
+impl DoubleEndedIterator for SeqIter {
+    fn next_back(&mut self) -> Option<f64> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(floatat(&self.spec, self.back))
+    }
+}
+
+impl ExactSizeIterator for SeqIter {}
+
+impl FusedIterator for SeqIter {}
+
+/// SeqDisplay displays the numbers of a [`Seq`], each followed by the
+/// separator, and then the terminator.
+#[derive(Debug)]
+pub struct SeqDisplay {
+    iter: SeqIter,
+    fmt: Format,
+    separator: String,
+    terminator: Option<String>,
+}
+
+impl fmt::Display for SeqDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for x in self.iter.clone() {
+            write!(f, "{}{}", self.fmt.render(x), self.separator)?;
+        }
+        if let Some(term) = &self.terminator {
+            write!(f, "{}", term)?;
+        }
+        Ok(())
+    }
+}
+
+#[path = "iter_test.rs"]
+#[cfg(test)]
+mod test;
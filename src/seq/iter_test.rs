@@ -0,0 +1,155 @@
+use super::*;
+use crate::seq::{emitseq, Separators};
+
+#[test]
+fn test_iter() -> Result<()> {
+    for (seq, exp) in [
+        (Seq::new(1.0, 3.0), vec![1.0, 2.0, 3.0]),
+        (Seq::new(3.0, 1.0), vec![3.0, 2.0, 1.0]),
+        (Seq::new(1.0, 1.0), vec![1.0]),
+        (Seq::new(1.0, 2.0).incr(0.5), vec![1.0, 1.5, 2.0]),
+        (Seq::new(1.0, 2.2).incr(0.5), vec![1.0, 1.5, 2.0]),
+        (Seq::new(0.0, -1.0).incr(-0.5), vec![0.0, -0.5, -1.0]),
+        (Seq::new(-1.0, 1.0), vec![-1.0, 0.0, 1.0]),
+        (Seq::new(0.0, 0.3).incr(0.1), vec![0.0, 0.1, 0.2, 0.3]),
+        (
+            Seq::new(0.0, 0.3).incr(0.1).precision(0),
+            vec![0.0, 0.1, 0.2, 0.3],
+        ),
+    ] {
+        let iter = seq.iter()?;
+        assert_eq!(iter.len(), exp.len(), "Should get length of {:?}", seq);
+        assert_eq!(
+            iter.clone().collect::<Vec<_>>(),
+            exp,
+            "Should iterate {:?}",
+            seq
+        );
+        assert_eq!(
+            iter.rev().collect::<Vec<_>>(),
+            exp.iter().rev().copied().collect::<Vec<_>>(),
+            "Should iterate {:?} in reverse",
+            seq
+        );
+    }
+
+    // Take from both ends.
+    let mut iter = Seq::new(1.0, 5.0).iter()?;
+    assert_eq!(iter.next(), Some(1.0));
+    assert_eq!(iter.next_back(), Some(5.0));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back(), Some(4.0));
+    assert_eq!(iter.next(), Some(2.0));
+    assert_eq!(iter.next(), Some(3.0));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.len(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_bad_iter() {
+    for (seq, err) in [
+        (Seq::new(1.0, 2.0).incr(0.0), "seq: zero increment"),
+        (Seq::new(2.0, 1.0).incr(0.0), "seq: zero decrement"),
+        (
+            Seq::new(1.0, 2.0).incr(-1.0),
+            "seq: needs positive increment",
+        ),
+        (
+            Seq::new(2.0, 1.0).incr(1.0),
+            "seq: needs negative decrement",
+        ),
+        (Seq::new(1.0, f64::INFINITY), "seq: sequence too long"),
+        (
+            Seq::new(f64::NAN, 1.0),
+            "seq: invalid not-a-number argument: NaN",
+        ),
+        (
+            Seq::new(1.0, 2.0).incr(f64::NAN),
+            "seq: invalid not-a-number argument: NaN",
+        ),
+    ] {
+        match seq.iter() {
+            Err(e) => assert_eq!(e.to_string(), err, "Should get error for {:?}", seq),
+            Ok(_) => panic!("Should get error for {:?}", seq),
+        }
+    }
+}
+
+#[test]
+fn test_emitseq_endpoints() -> Result<()> {
+    // The iterator must produce exactly the numbers emitseq prints.
+    for (first, incr, last) in [
+        (0.0, 0.1, 1.0),
+        (0.0, 0.1, 0.3),
+        (1.0, 0.2, 2.0),
+        (-1.0, 0.3, 1.0),
+        (1.0, -0.1, 0.0),
+        (1e15, 1.0, 1e15 + 3.0),
+        (0.0, 1.0 / 3.0, 1.0),
+    ] {
+        let seq = Seq::new(first, last).incr(incr);
+        let fmt = Format::fixed(1, 17);
+        let mut buf: Vec<u8> = vec![];
+        let seps = Separators {
+            sep: ",".into(),
+            term: None,
+            between: false,
+        };
+        emitseq(&mut buf, &seq.spec()?, &fmt, &seps)?;
+        assert_eq!(
+            seq.precision(17).separator(",").display()?.to_string(),
+            String::from_utf8(buf).unwrap(),
+            "Should match emitseq for {} {} {}",
+            first,
+            incr,
+            last
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_display() -> Result<()> {
+    for (seq, exp) in [
+        (Seq::new(1.0, 3.0), "1\n2\n3\n"),
+        (
+            Seq::new(1.0, 2.0).incr(0.25),
+            "1.00\n1.25\n1.50\n1.75\n2.00\n",
+        ),
+        (Seq::new(8.0, 10.0).equal_width(true), "08\n09\n10\n"),
+        (Seq::new(-1.0, 1.0).equal_width(true), "-1\n00\n01\n"),
+        (Seq::new(1.0, 2.0).precision(2), "1.00\n2.00\n"),
+        (Seq::new(1.0, 2.0).format("%.1e"), "1.0e+00\n2.0e+00\n"),
+        (Seq::new(1.0, 3.0).separator(","), "1,2,3,"),
+        (
+            Seq::new(1.0, 3.0).separator(",").terminator("\n"),
+            "1,2,3,\n",
+        ),
+        (Seq::new(0.0, 0.3).incr(0.1), "0.0\n0.1\n0.2\n0.3\n"),
+    ] {
+        assert_eq!(seq.display()?.to_string(), exp, "Should display {:?}", seq);
+    }
+
+    for (seq, err) in [
+        (
+            Seq::new(1.0, 2.0).format("%g").equal_width(true),
+            "seq: format string may not be specified when printing equal width strings",
+        ),
+        (
+            Seq::new(1.0, 2.0).format("%d"),
+            "seq: format '%d' has unknown %d directive",
+        ),
+        (Seq::new(1.0, 2.0).incr(0.0), "seq: zero increment"),
+    ] {
+        match seq.display() {
+            Err(e) => assert_eq!(e.to_string(), err, "Should get error for {:?}", seq),
+            Ok(_) => panic!("Should get error for {:?}", seq),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+/// Parses a numeric operand: a decimal floating point number, a C99
+/// hexadecimal floating point number such as `0x1.8p3`, an integer with a
+/// `0x`, `0o`, or `0b` prefix, `inf`, or `nan`. Digits may be grouped with
+/// underscores, as in `1_000`. Returns the number and, for an integer
+/// written with a radix prefix, its radix.
+pub fn parse(s: &str) -> Option<(f64, Option<u32>)> {
+    let s = ungroup(s.trim())?;
+    let (neg, digits) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, &s[..]),
+    };
+    let sign = if neg { -1.0 } else { 1.0 };
+
+    let radix = match digits.get(..2).map(|p| p.to_ascii_lowercase()).as_deref() {
+        Some("0x") => 16,
+        Some("0o") => 8,
+        Some("0b") => 2,
+        _ => return s.parse().ok().map(|x| (x, None)),
+    };
+    let digits = &digits[2..];
+
+    if radix == 16 && digits.contains(['.', 'p', 'P']) {
+        return hexfloat(digits).map(|x| (sign * x, None));
+    }
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    // Round the exact integer once, rather than at every digit.
+    let num = match u128::from_str_radix(digits, radix) {
+        Ok(num) => num as f64,
+        Err(_) => digits.chars().fold(0.0, |acc, c| {
+            acc * radix as f64 + c.to_digit(radix).unwrap() as f64
+        }),
+    };
+    Some((sign * num, Some(radix)))
+}
+
+/// Parses an integer operand written in decimal digits or with a `0x`, `0o`,
+/// or `0b` prefix, exactly. Digits may be grouped with underscores. Returns
+/// None for anything else, or for an integer out of the range of i128.
+pub fn integer(s: &str) -> Option<i128> {
+    let s = ungroup(s.trim())?;
+    let (neg, digits) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, &s[..]),
+    };
+    let (radix, digits) = match digits.get(..2).map(|p| p.to_ascii_lowercase()).as_deref() {
+        Some("0x") => (16, &digits[2..]),
+        Some("0o") => (8, &digits[2..]),
+        Some("0b") => (2, &digits[2..]),
+        _ => (10, digits),
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    let sign = if neg { "-" } else { "" };
+    i128::from_str_radix(&format!("{}{}", sign, digits), radix).ok()
+}
+
+/// Removes underscores that separate digits, as in `1_000_000`. Returns None
+/// if an underscore does not fall between two digits of a number.
+pub fn ungroup(s: &str) -> Option<String> {
+    if !s.contains('_') {
+        return Some(s.to_string());
+    }
+
+    // Hexadecimal numbers may have letters as digits.
+    let hex = s
+        .trim_start_matches(['-', '+'])
+        .get(..2)
+        .is_some_and(|p| p.eq_ignore_ascii_case("0x"));
+    let digit =
+        |c: Option<char>| c.is_some_and(|c| c.is_ascii_digit() || hex && c.is_ascii_hexdigit());
+
+    let chars: Vec<char> = s.chars().collect();
+    for (i, c) in chars.iter().enumerate() {
+        if *c == '_' && !(i > 0 && digit(Some(chars[i - 1])) && digit(chars.get(i + 1).copied())) {
+            return None;
+        }
+    }
+    Some(s.replace('_', ""))
+}
+
+// Parses the digits of a hexadecimal floating point number after the 0x,
+// such as "1.8p3" for 1.5 * 2^3.
+fn hexfloat(s: &str) -> Option<f64> {
+    let (mantissa, exp) = match s.find(['p', 'P']) {
+        Some(idx) => (&s[..idx], s[idx + 1..].parse::<i32>().ok()?),
+        None => (s, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if int.is_empty() && frac.is_empty()
+        || !int
+            .chars()
+            .chain(frac.chars())
+            .all(|c| c.is_ascii_hexdigit())
+    {
+        return None;
+    }
+
+    let num = int
+        .chars()
+        .chain(frac.chars())
+        .fold(0.0, |acc, c| acc * 16.0 + c.to_digit(16).unwrap() as f64);
+    Some(num * 2f64.powi(exp.saturating_sub(4 * frac.len() as i32)))
+}
+
+#[path = "number_test.rs"]
+#[cfg(test)]
+mod test;
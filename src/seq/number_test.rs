@@ -0,0 +1,89 @@
+use super::*;
+
+#[test]
+fn test_parse() {
+    for (num, exp) in [
+        ("0", (0.0, None)),
+        ("42", (42.0, None)),
+        (" -42 ", (-42.0, None)),
+        ("+.5", (0.5, None)),
+        ("1e3", (1000.0, None)),
+        ("-1.5e-3", (-0.0015, None)),
+        ("inf", (f64::INFINITY, None)),
+        ("-inf", (f64::NEG_INFINITY, None)),
+        ("1_000", (1000.0, None)),
+        ("1_000_000.000_5", (1000000.0005, None)),
+        ("0x10", (16.0, Some(16))),
+        ("0XfF", (255.0, Some(16))),
+        ("-0x10", (-16.0, Some(16))),
+        ("0xdead_beef", (3735928559.0, Some(16))),
+        ("0o17", (15.0, Some(8))),
+        ("0O17", (15.0, Some(8))),
+        ("0b101", (5.0, Some(2))),
+        ("-0b1111_0000", (-240.0, Some(2))),
+        ("0x3e7218072e8c35bf992d", (2.9489138216608238e+23, Some(16))),
+        (
+            "0xffffffffffffff00",
+            (18446744073709551360u64 as f64, Some(16)),
+        ),
+        ("0x1p3", (8.0, None)),
+        ("0x1.8p1", (3.0, None)),
+        ("0x.8", (0.5, None)),
+        ("-0xA.8P-1", (-5.25, None)),
+        ("0x1P+4", (16.0, None)),
+    ] {
+        assert_eq!(parse(num), Some(exp), "Should parse {}", num);
+    }
+    assert!(parse("nan").unwrap().0.is_nan());
+}
+
+#[test]
+fn test_bad_parse() {
+    for num in [
+        "", "-", "x", "1x", "0x", "0xg", "0o8", "0b2", "0b", "_1", "1_", "1__0", "1_.5", "1._5",
+        "0x_1", "0x1.8q1", "0x.p1", "0x1p", "1e_3", "--1",
+    ] {
+        assert_eq!(parse(num), None, "Should not parse {}", num);
+    }
+}
+
+#[test]
+fn test_integer() {
+    for (num, exp) in [
+        ("0", Some(0)),
+        (" -42 ", Some(-42)),
+        ("+1_000", Some(1000)),
+        ("9007199254740993", Some(9007199254740993)),
+        ("18446744073709551615", Some(18446744073709551615)),
+        ("0xffff_ffff_ffff_ff01", Some(0xffff_ffff_ffff_ff01)),
+        ("-0o17", Some(-15)),
+        ("0B101", Some(5)),
+        ("-170141183460469231731687303715884105728", Some(i128::MIN)),
+        ("170141183460469231731687303715884105728", None),
+        ("1.5", None),
+        ("1e3", None),
+        ("0x1p3", None),
+        ("inf", None),
+        ("-+1", None),
+        ("0x", None),
+        ("", None),
+    ] {
+        assert_eq!(integer(num), exp, "Should parse integer {}", num);
+    }
+}
+
+#[test]
+fn test_ungroup() {
+    for (num, exp) in [
+        ("1000", Some("1000")),
+        ("1_000", Some("1000")),
+        ("-1_000.000_1", Some("-1000.0001")),
+        ("0xab_cd", Some("0xabcd")),
+        ("ab_cd", None),
+        ("_1", None),
+        ("1_", None),
+        ("1__0", None),
+    ] {
+        assert_eq!(ungroup(num).as_deref(), exp, "Should ungroup {}", num);
+    }
+}
@@ -0,0 +1,159 @@
+use std::env;
+
+// Locales by language and territory, with their thousands separators and
+// decimal points, as in glibc.
+const LOCALES: &[(&str, &str, char)] = &[
+    ("C", "", '.'),
+    ("POSIX", "", '.'),
+    ("cs_CZ", "\u{a0}", ','),
+    ("da_DK", ".", ','),
+    ("de_AT", "\u{a0}", ','),
+    ("de_CH", "'", '.'),
+    ("de_DE", ".", ','),
+    ("en_AU", ",", '.'),
+    ("en_CA", ",", '.'),
+    ("en_GB", ",", '.'),
+    ("en_IE", ",", '.'),
+    ("en_NZ", ",", '.'),
+    ("en_US", ",", '.'),
+    ("es_ES", ".", ','),
+    ("es_MX", ",", '.'),
+    ("fi_FI", "\u{a0}", ','),
+    ("fr_BE", "\u{202f}", ','),
+    ("fr_CA", "\u{a0}", ','),
+    ("fr_CH", "\u{202f}", '.'),
+    ("fr_FR", "\u{202f}", ','),
+    ("it_IT", ".", ','),
+    ("ja_JP", ",", '.'),
+    ("ko_KR", ",", '.'),
+    ("nb_NO", "\u{a0}", ','),
+    ("nl_BE", ".", ','),
+    ("nl_NL", ".", ','),
+    ("pl_PL", "\u{a0}", ','),
+    ("pt_BR", ".", ','),
+    ("pt_PT", "\u{a0}", ','),
+    ("ru_RU", "\u{a0}", ','),
+    ("sv_SE", "\u{a0}", ','),
+    ("tr_TR", ".", ','),
+    ("uk_UA", "\u{a0}", ','),
+    ("zh_CN", ",", '.'),
+    ("zh_TW", ",", '.'),
+];
+
+/// Numeric determines how the digits of decimal numbers are written: the
+/// separator between groups of three integer digits, if any, and the
+/// character that separates the integer digits from the fraction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Numeric {
+    group: Option<String>,
+    point: char,
+}
+
+impl Default for Numeric {
+    fn default() -> Numeric {
+        Numeric {
+            group: None,
+            point: '.',
+        }
+    }
+}
+
+impl Numeric {
+    /// Returns the conventions of a locale name such as `de_DE.UTF-8` or
+    /// `fr_FR@euro`, or None if the locale is unknown. A name of `C` or
+    /// `POSIX` has no thousands separator.
+    pub fn locale(name: &str) -> Option<Numeric> {
+        let lang = name.split(['.', '@']).next()?;
+        LOCALES
+            .iter()
+            .find(|l| l.0 == lang)
+            .map(|(_, group, point)| Numeric {
+                group: Some(group.to_string()).filter(|g| !g.is_empty()),
+                point: *point,
+            })
+    }
+
+    /// Returns the conventions of the locale named by the LC_ALL,
+    /// LC_NUMERIC, or LANG environment variable, whichever is first set, or
+    /// of the C locale if it is unknown.
+    pub fn env() -> Numeric {
+        ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|val| !val.is_empty())
+            .and_then(|name| Numeric::locale(&name))
+            .unwrap_or_default()
+    }
+
+    /// Returns the conventions with groups of digits separated by sep.
+    pub fn group(self, sep: &str) -> Numeric {
+        Numeric {
+            group: Some(sep.to_string()),
+            ..self
+        }
+    }
+
+    /// Returns the conventions without grouping.
+    pub fn ungroup(self) -> Numeric {
+        Numeric {
+            group: None,
+            ..self
+        }
+    }
+
+    /// Returns the thousands separator of the conventions, if any.
+    pub fn separator(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    /// Returns the conventions with the decimal point c.
+    pub fn point(self, c: char) -> Numeric {
+        Numeric { point: c, ..self }
+    }
+
+    /// Rewrites a number formatted with ASCII digits and a `.` decimal point,
+    /// as in `-1234.5` or `1.5e+03`, in the conventions. Zeros are added to
+    /// the integer digits until the number is at least width characters
+    /// long, so that zero padding is grouped like the digits it precedes.
+    pub fn apply(&self, num: &str, width: usize) -> String {
+        let sign = num.len() - num.trim_start_matches(['-', '+', ' ']).len();
+        let digits = num[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .map_or(num.len(), |idx| sign + idx);
+        let rest = &num[digits..];
+        let rest = match rest.strip_prefix('.') {
+            Some(frac) if self.point != '.' => format!("{}{}", self.point, frac),
+            _ => rest.to_string(),
+        };
+
+        let mut int = num[sign..digits].to_string();
+        if int.is_empty() {
+            return format!("{}{}", &num[..sign], rest);
+        }
+        let len = |int: &str| sign + self.grouped(int).chars().count() + rest.chars().count();
+        while len(&int) < width {
+            int.insert(0, '0');
+        }
+        format!("{}{}{}", &num[..sign], self.grouped(&int), rest)
+    }
+
+    // Separates a run of digits into groups of three from the right.
+    fn grouped(&self, int: &str) -> String {
+        let sep = match &self.group {
+            Some(sep) => sep,
+            None => return int.to_string(),
+        };
+        let mut s = String::with_capacity(int.len() + int.len() / 3 * sep.len());
+        for (i, c) in int.chars().enumerate() {
+            if i > 0 && (int.len() - i).is_multiple_of(3) {
+                s.push_str(sep);
+            }
+            s.push(c);
+        }
+        s
+    }
+}
+
+#[path = "numeric_test.rs"]
+#[cfg(test)]
+mod test;
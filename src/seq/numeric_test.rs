@@ -0,0 +1,54 @@
+use super::*;
+
+#[test]
+fn test_locale() {
+    for (name, exp) in [
+        ("C", Some(Numeric::default())),
+        ("POSIX", Some(Numeric::default())),
+        ("en_US.UTF-8", Some(Numeric::default().group(","))),
+        ("de_DE", Some(Numeric::default().group(".").point(','))),
+        (
+            "fr_FR@euro",
+            Some(Numeric::default().group("\u{202f}").point(',')),
+        ),
+        ("de_CH.UTF-8", Some(Numeric::default().group("'"))),
+        ("xx_XX", None),
+        ("", None),
+    ] {
+        assert_eq!(Numeric::locale(name), exp, "Should get locale {:?}", name);
+    }
+}
+
+#[test]
+fn test_apply() {
+    let c = Numeric::default();
+    let en = c.clone().group(",");
+    let de = c.clone().group(".").point(',');
+    for (numeric, num, width, exp) in [
+        (&c, "1234.5", 0, "1234.5"),
+        (&en, "1", 0, "1"),
+        (&en, "999", 0, "999"),
+        (&en, "1000", 0, "1,000"),
+        (&en, "1234567.891", 0, "1,234,567.891"),
+        (&en, "-1234", 0, "-1,234"),
+        (&en, "1.5e+03", 0, "1.5e+03"),
+        (&en, "inf", 0, "inf"),
+        (&de, "1234567.5", 0, "1.234.567,5"),
+        (&de, "0.25", 0, "0,25"),
+        (&c.clone().point(','), "1234.5", 0, "1234,5"),
+        (&en, "1", 5, "0,001"),
+        (&en, "1", 6, "00,001"),
+        (&en, "-500", 6, "-0,500"),
+        (&en, "1000", 3, "1,000"),
+        (&c, "1", 3, "001"),
+    ] {
+        assert_eq!(
+            numeric.apply(num, width),
+            exp,
+            "Should apply {:?} to {} at width {}",
+            numeric,
+            num,
+            width
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use super::{Result, SeqError};
+use std::io::{self, Write};
+
+/// Output is a structured output format, as selected by `seq --output`.
+#[derive(Debug, PartialEq)]
+pub enum Output {
+    // A JSON array of values.
+    Json,
+    // One JSON object per line, with the index and value of an item.
+    Ndjson,
+    // A CSV column of values, with an optional header.
+    Csv(Option<String>),
+}
+
+impl Output {
+    /// Parses the name of an output format: `json`, `ndjson`, or `csv`. A
+    /// header names the column of a CSV.
+    pub fn parse(name: &str, header: Option<String>) -> Result<Output> {
+        match (name, header) {
+            ("json", None) => Ok(Output::Json),
+            ("ndjson", None) => Ok(Output::Ndjson),
+            ("csv", header) => Ok(Output::Csv(header)),
+            ("json" | "ndjson", Some(_)) => Err(SeqError::Usage(Some(
+                "--header requires --output=csv".to_string(),
+            ))),
+            (name, _) => Err(SeqError::InvalidArgument(format!(
+                "invalid output format: {}",
+                name
+            ))),
+        }
+    }
+
+    /// Writes what precedes the first item.
+    pub fn begin(&self, out: &mut impl Write) -> io::Result<()> {
+        match self {
+            Output::Json => write!(out, "["),
+            Output::Ndjson => Ok(()),
+            Output::Csv(None) => Ok(()),
+            Output::Csv(Some(header)) => writeln!(out, "{}", csv(header)),
+        }
+    }
+
+    /// Writes an item, where index is its position in the output.
+    pub fn item(&self, out: &mut impl Write, index: u64, value: &str) -> io::Result<()> {
+        match self {
+            Output::Json if index == 0 => write!(out, "{}", json(value)),
+            Output::Json => write!(out, ",{}", json(value)),
+            Output::Ndjson => writeln!(out, "{{\"index\":{},\"value\":{}}}", index, json(value)),
+            Output::Csv(_) => writeln!(out, "{}", csv(value)),
+        }
+    }
+
+    /// Writes what precedes the first record of a number of columns. A CSV
+    /// header names each column with the header and its number, as in
+    /// `value1,value2`.
+    pub fn begin_records(&self, out: &mut impl Write, columns: usize) -> io::Result<()> {
+        match self {
+            Output::Csv(Some(header)) => {
+                let names: Vec<String> = (1..=columns)
+                    .map(|i| csv(&format!("{}{}", header, i)))
+                    .collect();
+                writeln!(out, "{}", names.join(","))
+            }
+            _ => self.begin(out),
+        }
+    }
+
+    /// Writes a record of several values, where index is its position in the
+    /// output: a JSON array of the values, or a CSV line with one field each.
+    pub fn record(&self, out: &mut impl Write, index: u64, values: &[String]) -> io::Result<()> {
+        let array = || {
+            let values: Vec<String> = values.iter().map(|x| json(x)).collect();
+            format!("[{}]", values.join(","))
+        };
+        match self {
+            Output::Json if index == 0 => write!(out, "{}", array()),
+            Output::Json => write!(out, ",{}", array()),
+            Output::Ndjson => writeln!(out, "{{\"index\":{},\"value\":{}}}", index, array()),
+            Output::Csv(_) => {
+                let fields: Vec<String> = values.iter().map(|x| csv(x)).collect();
+                writeln!(out, "{}", fields.join(","))
+            }
+        }
+    }
+
+    /// Writes what follows the last item.
+    pub fn end(&self, out: &mut impl Write) -> io::Result<()> {
+        match self {
+            Output::Json => writeln!(out, "]"),
+            _ => Ok(()),
+        }
+    }
+}
+
+// Returns a value as a JSON number if it is one, such as 1.50 or -2e+03, or
+// else as a JSON string, such as "007", "0x1f", "inf", or "2026-01-01".
+fn json(value: &str) -> String {
+    if is_number(value) {
+        return value.to_string();
+    }
+
+    let mut s = String::with_capacity(value.len() + 2);
+    s.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            c if c < ' ' => s.push_str(&format!("\\u{:04x}", c as u32)),
+            c => s.push(c),
+        }
+    }
+    s.push('"');
+    s
+}
+
+// Returns true if s matches the JSON number grammar:
+// -?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?
+fn is_number(s: &str) -> bool {
+    let digits = |s: &str| s.len() - s.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+    let s = s.strip_prefix('-').unwrap_or(s);
+    let int = digits(s);
+    if int == 0 || int > 1 && s.starts_with('0') {
+        return false;
+    }
+    let mut s = &s[int..];
+    if let Some(frac) = s.strip_prefix('.') {
+        let n = digits(frac);
+        if n == 0 {
+            return false;
+        }
+        s = &frac[n..];
+    }
+    if let Some(exp) = s.strip_prefix(['e', 'E']) {
+        let exp = exp.strip_prefix(['+', '-']).unwrap_or(exp);
+        let n = digits(exp);
+        if n == 0 {
+            return false;
+        }
+        s = &exp[n..];
+    }
+    s.is_empty()
+}
+
+// Quotes a CSV field if it contains a comma, quote, or line break.
+fn csv(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[path = "output_test.rs"]
+#[cfg(test)]
+mod test;
@@ -0,0 +1,149 @@
+use super::*;
+
+#[test]
+fn test_parse() -> Result<()> {
+    for (name, header, exp) in [
+        ("json", None, Output::Json),
+        ("ndjson", None, Output::Ndjson),
+        ("csv", None, Output::Csv(None)),
+        ("csv", Some("n"), Output::Csv(Some("n".to_string()))),
+    ] {
+        assert_eq!(
+            Output::parse(name, header.map(String::from))?,
+            exp,
+            "Should parse {}",
+            name
+        );
+    }
+
+    for (name, header, err) in [
+        ("xml", None, "seq: invalid output format: xml"),
+        ("", None, "seq: invalid output format: "),
+        ("json", Some("n"), "seq: --header requires --output=csv"),
+        ("ndjson", Some("n"), "seq: --header requires --output=csv"),
+    ] {
+        match Output::parse(name, header.map(String::from)) {
+            Err(e) => assert_eq!(e.to_string(), err, "Should get error for {}", name),
+            Ok(_) => panic!("Should get error for {}", name),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_is_number() {
+    for s in [
+        "0", "-0", "1", "42", "0.5", "-1.50", "1e5", "1E+05", "2.5e-3",
+    ] {
+        assert!(is_number(s), "Should be a number: {}", s);
+    }
+    for s in [
+        "", "-", "01", "-01", ".5", "1.", "1e", "1e+", "+1", "inf", "nan", "0x1f", "1,000", "1 ",
+    ] {
+        assert!(!is_number(s), "Should not be a number: {}", s);
+    }
+}
+
+#[test]
+fn test_json() {
+    for (value, exp) in [
+        ("1.5", "1.5"),
+        ("007", "\"007\""),
+        ("-inf", "\"-inf\""),
+        ("2026-01-01", "\"2026-01-01\""),
+        ("a\"b\\c", "\"a\\\"b\\\\c\""),
+        ("\n\t\u{1}", "\"\\n\\t\\u0001\""),
+    ] {
+        assert_eq!(json(value), exp, "Should encode {:?}", value);
+    }
+}
+
+#[test]
+fn test_csv() {
+    for (value, exp) in [
+        ("1.5", "1.5"),
+        ("1,000", "\"1,000\""),
+        ("say \"hi\"", "\"say \"\"hi\"\"\""),
+        ("a\nb", "\"a\nb\""),
+    ] {
+        assert_eq!(csv(value), exp, "Should encode {:?}", value);
+    }
+}
+
+#[test]
+fn test_write() -> Result<()> {
+    for (output, items, exp) in [
+        (Output::Json, vec![], "[]\n"),
+        (Output::Json, vec!["1", "2", "x"], "[1,2,\"x\"]\n"),
+        (Output::Ndjson, vec![], ""),
+        (
+            Output::Ndjson,
+            vec!["1", "02"],
+            "{\"index\":0,\"value\":1}\n{\"index\":1,\"value\":\"02\"}\n",
+        ),
+        (Output::Csv(None), vec!["1", "2"], "1\n2\n"),
+        (Output::Csv(Some("n".to_string())), vec![], "n\n"),
+        (
+            Output::Csv(Some("a,b".to_string())),
+            vec!["1"],
+            "\"a,b\"\n1\n",
+        ),
+    ] {
+        let mut buf: Vec<u8> = vec![];
+        output.begin(&mut buf)?;
+        for (i, item) in items.iter().enumerate() {
+            output.item(&mut buf, i as u64, item)?;
+        }
+        output.end(&mut buf)?;
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            exp,
+            "Should write {:?} as {:?}",
+            items,
+            output
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_write_records() -> Result<()> {
+    for (output, records, exp) in [
+        (Output::Json, vec![], "[]\n"),
+        (
+            Output::Json,
+            vec![vec!["1", "x"], vec!["2", "y"]],
+            "[[1,\"x\"],[2,\"y\"]]\n",
+        ),
+        (
+            Output::Ndjson,
+            vec![vec!["1", "02"]],
+            "{\"index\":0,\"value\":[1,\"02\"]}\n",
+        ),
+        (Output::Csv(None), vec![vec!["1", "a,b"]], "1,\"a,b\"\n"),
+        (
+            Output::Csv(Some("n".to_string())),
+            vec![vec!["1", "2"]],
+            "n1,n2\n1,2\n",
+        ),
+    ] {
+        let mut buf: Vec<u8> = vec![];
+        output.begin_records(&mut buf, 2)?;
+        for (i, record) in records.iter().enumerate() {
+            let values: Vec<String> = record.iter().map(|x| x.to_string()).collect();
+            output.record(&mut buf, i as u64, &values)?;
+        }
+        output.end(&mut buf)?;
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            exp,
+            "Should write {:?} as {:?}",
+            records,
+            output
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,1652 @@
+use crate::crab_core::{self, CrabError, Utility};
+use getopts::{Matches, Options};
+use std::{
+    cmp,
+    collections::hash_map::RandomState,
+    convert::TryFrom,
+    hash::{BuildHasher, Hasher},
+    io::Write,
+    process::ExitCode,
+    result, str,
+};
+
+mod date;
+mod decimal;
+mod escape;
+mod format;
+mod number;
+mod numeric;
+mod output;
+mod shuffle;
+use date::{DateFormat, DateTime, Duration};
+use decimal::{Decimal, MAX_DIGITS};
+use format::Format;
+use numeric::Numeric;
+use output::Output;
+use shuffle::Permutation;
+
+/// Result is the result of a seq operation, with errors formatted as the
+/// seq utility prints them.
+pub type Result<T> = result::Result<T, SeqError>;
+type Exact = SeqSpec<Decimal>;
+type Ints = SeqSpec<i128>;
+type Dates = (DateTime, Duration, DateTime, bool);
+type Column = (u64, Box<dyn Fn(u64) -> String>);
+
+// Compat identifies the seq implementation whose output to emulate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Compat {
+    // Always print at the greatest precision of the operands.
+    Crab,
+    // Like Crab, but print non-decimal operands such as inf with %g.
+    Gnu,
+    // Print with %g unless -w is set.
+    Bsd,
+}
+
+// Order identifies the order in which to emit the items of a sequence.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Order {
+    Forward,
+    Reverse,
+    // A pseudorandom permutation determined by the seed.
+    Shuffle(u64),
+}
+
+// Separators determines the text written around the items of a sequence.
+#[derive(Clone, Debug, PartialEq)]
+struct Separators {
+    // Written after each item, or only between items if between is set.
+    sep: String,
+    // Written after the sequence, or if between is set, a newline by default.
+    term: Option<String>,
+    between: bool,
+}
+
+impl Separators {
+    // Returns the text to write before the nth item written.
+    fn before(&self, n: u64) -> &str {
+        if self.between && n > 0 {
+            &self.sep
+        } else {
+            ""
+        }
+    }
+
+    // Returns the text to write after each item.
+    fn after(&self) -> &str {
+        if self.between {
+            ""
+        } else {
+            &self.sep
+        }
+    }
+
+    // Returns the text to write after a sequence of n items.
+    fn end(&self, n: u64) -> &str {
+        match &self.term {
+            Some(term) => term,
+            None if self.between && n > 0 => "\n",
+            None => "",
+        }
+    }
+}
+
+// Pad identifies the character with which to pad numbers to a width.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Pad {
+    Zero,
+    Space,
+}
+
+// The size at which emitints flushes its output buffer.
+const BUFSIZE: usize = 64 * 1024;
+
+macro_rules! usage {
+    () => {
+        "Usage: seq [-w] [-f format] [-s string] [-t string] [first [incr]] last"
+    };
+}
+
+macro_rules! width {
+    ($x:expr) => {
+        cmp::max(
+            format!("{0:.1$}", $x.first, $x.precision).len(),
+            format!("{0:.1$}", $x.last, $x.precision).len(),
+        )
+    };
+    ($x:expr, numeric $n:expr) => {
+        cmp::max(
+            $n.apply(&format!("{0:.1$}", $x.first, $x.precision), 0)
+                .chars()
+                .count(),
+            $n.apply(&format!("{0:.1$}", $x.last, $x.precision), 0)
+                .chars()
+                .count(),
+        )
+    };
+    ($x:expr, $fmt:expr) => {
+        cmp::max(
+            $fmt.render($x.first).chars().count(),
+            $fmt.render($x.last).chars().count(),
+        )
+    };
+}
+
+// Declared after the macros so that they may use them.
+mod error;
+mod iter;
+pub use error::SeqError;
+pub use iter::{Seq, SeqDisplay, SeqIter, SeqSpec};
+
+/// SeqUtility is the seq utility, as run by the `seq` and `crab` binaries.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SeqUtility;
+
+impl Utility for SeqUtility {
+    fn name(&self) -> &'static str {
+        "seq"
+    }
+
+    fn run(&self, mut out: &mut dyn Write, err: &mut dyn Write, argv: &[String]) -> ExitCode {
+        let res = run(&mut out, argv).and_then(|()| out.flush().map_err(SeqError::from));
+        crab_core::report(err, res.map_err(CrabError::from))
+    }
+}
+
+/// Runs the seq utility with the command line arguments, not including the
+/// program name, writing the sequence to out.
+pub fn run(out: &mut impl Write, argv: &[String]) -> Result<()> {
+    let opts = options();
+    let opt = operands(&opts, argv)
+        .and_then(|args| opts.parse(args))
+        .map_err(|e| SeqError::Usage(Some(format!("{}\n{}", e, usage!()))))?;
+    if crab_core::help_or_version(out, "seq", usage!(), &opts, &opt)? {
+        return Ok(());
+    }
+
+    let compat = getcompat(&opt)?;
+    let order = getorder(&opt)?;
+    let count = getcount(&opt)?;
+    let by = opt.opt_str("by");
+    let seps = getseparators(&opt, compat)?;
+    if opt.opt_present("date") {
+        return emitdates(out, &opt, count, by.as_deref(), order, &seps);
+    }
+    if opt.opt_present("product") {
+        return emitproduct(out, &opt, compat, order, &seps);
+    }
+
+    let output = getoutput(&opt)?;
+    let numeric = getnumeric(&opt, false)?;
+    let pad = getpad(&opt)?;
+    let exclude = opt.opt_present("exclude-last");
+    let geometric = ["geometric", "multiply", "logspace"]
+        .iter()
+        .any(|x| opt.opt_present(x));
+    let seq = if geometric {
+        getgeoseq(
+            &opt.free,
+            count,
+            by.as_deref(),
+            exclude,
+            opt.opt_present("logspace"),
+        )?
+    } else if let Some(n) = count {
+        getcountseq(&opt.free, n, by.as_deref(), exclude)?
+    } else {
+        getseq(&opt.free)?
+    };
+    let ints = if geometric {
+        None
+    } else {
+        getints(&opt.free, count, by.as_deref())
+    };
+    let fmt = getformat(&opt, compat, &opt.free, &seq, ints.as_ref())?;
+    if count.is_none() && !geometric && gnuempty(compat, &opt.free, &seq) {
+        return emitorder(out, 0, order, |_| String::new(), &seps, output.as_ref());
+    }
+
+    // Count in exact integers in a radix, so that large numbers do not lose
+    // precision to floating point.
+    if let (Some(ints), Some(true)) = (&ints, fmt.as_ref().map(Format::is_radix)) {
+        let fmt = fmt.unwrap().zero(pad == Pad::Zero);
+        return emitorder(
+            out,
+            count.map_or_else(|| intcount(ints), Ok)?,
+            order,
+            |i| fmt.render_int(intat(ints, i)),
+            &seps,
+            output.as_ref(),
+        );
+    }
+
+    // Use exact arithmetic with the default format.
+    if fmt.is_none() && !geometric {
+        let exact = match count {
+            Some(n) => getexactcount(&opt.free, n, by.as_deref()),
+            None => getexact(&opt.free),
+        };
+        if let Some(exact) = exact {
+            let width = getwidth(&opt, || Ok(width!(exact, numeric numeric)))?;
+            let fmt = Format::fixed(width, exact.precision)
+                .numeric(&numeric)
+                .zero(pad == Pad::Zero);
+            return match order {
+                Order::Forward
+                    if count.is_none()
+                        && output.is_none()
+                        && numeric == Numeric::default()
+                        && pad == Pad::Zero
+                        && exact.precision == 0
+                        && !(exact.first.is_negative()
+                            || exact.incr.is_negative()
+                            || exact.last.is_negative()) =>
+                {
+                    let (first, incr, last) = (
+                        exact.first.to_string(),
+                        exact.incr.to_string(),
+                        exact.last.to_string(),
+                    );
+                    emitints(out, &first, &incr, &last, width, &seps)
+                }
+                Order::Forward if count.is_none() && output.is_none() => {
+                    emitexact(out, &exact, &fmt, &seps)
+                }
+                _ => emitorder(
+                    out,
+                    count.map_or_else(|| exactcount(&exact), Ok)?,
+                    order,
+                    |i| fmt.render_decimal(&exactat(&exact, i)),
+                    &seps,
+                    output.as_ref(),
+                ),
+            };
+        }
+    }
+
+    let fmt = match fmt {
+        Some(fmt) => fmt,
+        None if geometric => {
+            // Print up to 15 significant digits, as fixed precision is of
+            // little use for numbers that grow or shrink exponentially.
+            let width = getwidth(&opt, || {
+                Ok(width!(seq, Format::parse("%.15g")?.numeric(&numeric)))
+            })?;
+            Format::parse(&format!("%0{}.15g", width))?.numeric(&numeric)
+        }
+        None if compat == Compat::Gnu => Format::parse("%g")?.numeric(&numeric),
+        None => Format::fixed(
+            getwidth(&opt, || Ok(width!(seq, numeric numeric)))?,
+            seq.precision,
+        )
+        .numeric(&numeric),
+    }
+    .zero(pad == Pad::Zero);
+    match order {
+        Order::Forward if count.is_none() && output.is_none() && !geometric => {
+            emitseq(out, &seq, &fmt, &seps)
+        }
+        // Stream the items rather than count them, as the sequence may be
+        // endless.
+        Order::Forward if count.is_none() && !geometric => emititems(
+            out,
+            (0..)
+                .map(|i| floatat(&seq, i))
+                .take_while(|x| {
+                    if seq.incr < 0.0 {
+                        *x >= seq.last
+                    } else {
+                        *x <= seq.last
+                    }
+                })
+                .map(|x| fmt.render(x)),
+            &seps,
+            output.as_ref(),
+        ),
+        _ if geometric => emitorder(
+            out,
+            count.map_or_else(|| geocount(&seq), Ok)?,
+            order,
+            |i| fmt.render(geoat(&seq, i)),
+            &seps,
+            output.as_ref(),
+        ),
+        _ => emitorder(
+            out,
+            count.map_or_else(|| floatcount(&seq), Ok)?,
+            order,
+            |i| fmt.render(floatat(&seq, i)),
+            &seps,
+            output.as_ref(),
+        ),
+    }
+}
+
+fn options() -> Options {
+    let mut opts = Options::new();
+    opts.optflag("w", "equal-width", "pad numbers with zeros to equal width");
+    opts.optopt(
+        "f",
+        "format",
+        "print numbers with a printf(3) format",
+        "FORMAT",
+    );
+    opts.optopt(
+        "s",
+        "separator",
+        "separate numbers with STRING (default \\n)",
+        "STRING",
+    );
+    opts.optopt(
+        "t",
+        "terminator",
+        "terminate the sequence with STRING",
+        "STRING",
+    );
+    opts.optflag("", "raw", "do not interpret escapes in -s and -t");
+    opts.optopt(
+        "",
+        "compat",
+        "emulate the output of crab, gnu, or bsd seq",
+        "MODE",
+    );
+    opts.optopt(
+        "",
+        "radix",
+        "print integers in base 2, 8, 10, or 16",
+        "RADIX",
+    );
+    opts.optflag("x", "hex", "print integers in hexadecimal");
+    opts.optflag("o", "octal", "print integers in octal");
+    opts.optflag("b", "binary", "print integers in binary");
+    opts.optflag(
+        "",
+        "prefix",
+        "precede numbers in a radix with 0x, 0o, or 0b",
+    );
+    opts.optflag("", "scientific", "print numbers in scientific notation");
+    opts.optflag("", "reverse", "print the sequence in reverse order");
+    opts.optflagopt(
+        "",
+        "shuffle",
+        "print the sequence in a pseudorandom order",
+        "SEED",
+    );
+    opts.optopt("n", "count", "print COUNT numbers", "COUNT");
+    opts.optopt("", "by", "with -n, increment numbers by INCR", "INCR");
+    opts.optflag(
+        "",
+        "exclude-last",
+        "with -n, space numbers as if one more and omit last",
+    );
+    opts.optflag(
+        "",
+        "geometric",
+        "multiply each number by incr to get the next",
+    );
+    opts.optflag("", "multiply", "same as --geometric");
+    opts.optflag(
+        "",
+        "logspace",
+        "with -n, space numbers evenly on a logarithmic scale",
+    );
+    opts.optflag("", "date", "print a sequence of ISO 8601 dates");
+    opts.optopt(
+        "",
+        "output",
+        "print the sequence as json, ndjson, or csv",
+        "FORMAT",
+    );
+    opts.optflagopt(
+        "",
+        "header",
+        "with --output=csv, print a header line (default value)",
+        "NAME",
+    );
+    opts.optflag("", "product", "print the cartesian product of ranges");
+    opts.optopt(
+        "d",
+        "delimiter",
+        "with --product, separate tuple numbers with STRING",
+        "STRING",
+    );
+    opts.optflagopt(
+        "",
+        "group",
+        "separate groups of three digits with SEP",
+        "SEP",
+    );
+    opts.optopt(
+        "",
+        "decimal-point",
+        "separate the fraction with the character C",
+        "C",
+    );
+    opts.optflagopt(
+        "",
+        "locale",
+        "write numbers in the conventions of a locale",
+        "NAME",
+    );
+    opts.optopt(
+        "",
+        "width",
+        "pad numbers to at least WIDTH characters",
+        "WIDTH",
+    );
+    opts.optopt("", "pad", "pad numbers with zero or space", "FILL");
+    crab_core::standard_options(&mut opts);
+    opts
+}
+
+// Returns the output radix, if other than 10.
+fn getradix(opt: &Matches) -> Result<Option<u32>> {
+    let mut radix = match opt.opt_str("radix").as_deref() {
+        None => None,
+        Some("10") => Some(10),
+        Some("2") => Some(2),
+        Some("8") => Some(8),
+        Some("16") => Some(16),
+        Some(r) => return Err(SeqError::InvalidArgument(format!("invalid radix: {}", r))),
+    };
+    for (flag, base) in [("x", 16), ("o", 8), ("b", 2)] {
+        if opt.opt_present(flag) {
+            if radix.is_some_and(|r| r != base) {
+                return Err(SeqError::Usage(Some(
+                    "only one radix may be specified".to_string(),
+                )));
+            }
+            radix = Some(base);
+        }
+    }
+    Ok(radix)
+}
+
+// Returns the radix shared by all operands written with a radix prefix, such
+// as 0x10, provided that all of the operands are integers.
+fn detectradix(args: &[String]) -> Option<u32> {
+    let mut radix = None;
+    for arg in args {
+        match number::parse(arg)? {
+            (_, Some(r)) if radix.is_none() || radix == Some(r) => radix = Some(r),
+            (x, None) if x.fract() == 0.0 => (),
+            _ => return None,
+        }
+    }
+    radix
+}
+
+// Returns the format for the sequence if it should not be printed with the
+// default fixed precision format.
+fn getformat(
+    opt: &Matches,
+    compat: Compat,
+    args: &[String],
+    seq: &SeqSpec,
+    ints: Option<&Ints>,
+) -> Result<Option<Format>> {
+    if let Some(f) = opt.opt_str("f") {
+        exclusive(opt, "format", &["scientific", "width", "pad", "prefix"])?;
+        if opt.opt_present("w") {
+            return Err(SeqError::Usage(Some(
+                "format string may not be specified when printing equal width strings".to_string(),
+            )));
+        }
+        if getradix(opt)?.is_some() {
+            return Err(SeqError::Usage(Some(
+                "format string may not be specified with a radix".to_string(),
+            )));
+        }
+        let fmt = Format::parse(&f)?;
+        let numeric = getnumeric(opt, fmt.grouped())?;
+        return Ok(Some(fmt.numeric(&numeric)));
+    }
+
+    if opt.opt_present("scientific") {
+        // Items of geometric sequences have no common precision.
+        exclusive(
+            opt,
+            "scientific",
+            &[
+                "radix",
+                "x",
+                "o",
+                "b",
+                "prefix",
+                "geometric",
+                "multiply",
+                "logspace",
+            ],
+        )?;
+        let (places, numeric) = (sciplaces(seq), getnumeric(opt, false)?);
+        let width = getwidth(opt, || {
+            Ok(width!(
+                seq,
+                Format::parse(&format!("%.{}e", places))?.numeric(&numeric)
+            ))
+        })?;
+        return Ok(Some(
+            Format::parse(&format!("%0{}.{}e", width, places))?.numeric(&numeric),
+        ));
+    }
+
+    // Print in the radix of the operands unless told otherwise, or emulating
+    // GNU seq, which prints them in decimal.
+    let (radix, prefix) = match getradix(opt)? {
+        Some(radix) => (Some(radix), opt.opt_present("prefix")),
+        None if compat == Compat::Gnu => (None, true),
+        None => (detectradix(args), true),
+    };
+    if opt.opt_present("prefix") && radix.is_none_or(|r| r == 10) {
+        return Err(SeqError::Usage(Some(
+            "--prefix requires a radix other than 10".to_string(),
+        )));
+    }
+    if let Some(radix) = radix.filter(|r| *r != 10) {
+        if ints.is_none()
+            && [seq.first, seq.incr, seq.last]
+                .iter()
+                .any(|x| x.fract() != 0.0)
+        {
+            return Err(SeqError::InvalidArgument(
+                "radix output requires integer arguments".to_string(),
+            ));
+        }
+        let width = getwidth(opt, || {
+            let fmt = Format::radix(radix, 1, prefix);
+            Ok(match ints {
+                Some(ints) => cmp::max(
+                    fmt.render_int(ints.first).len(),
+                    fmt.render_int(ints.last).len(),
+                ),
+                None => width!(seq, fmt),
+            })
+        })?;
+        return Ok(Some(Format::radix(radix, width, prefix)));
+    }
+
+    if compat == Compat::Bsd && !opt.opt_present("w") && !opt.opt_present("width") {
+        return Ok(Some(Format::parse("%g")?.numeric(&getnumeric(opt, false)?)));
+    }
+    Ok(None)
+}
+
+fn getorder(opt: &Matches) -> Result<Order> {
+    match (opt.opt_present("reverse"), opt.opt_present("shuffle")) {
+        (true, true) => Err(SeqError::Usage(Some(
+            "cannot both reverse and shuffle".to_string(),
+        ))),
+        (true, false) => Ok(Order::Reverse),
+        (false, true) => match opt.opt_str("shuffle") {
+            Some(seed) => Ok(Order::Shuffle(seed.parse().map_err(|_| {
+                SeqError::InvalidArgument(format!("invalid shuffle seed: {}", seed))
+            })?)),
+            None => Ok(Order::Shuffle(RandomState::new().build_hasher().finish())),
+        },
+        (false, false) => Ok(Order::Forward),
+    }
+}
+
+// Returns the separator and terminator set by -s and -t, with their escapes
+// interpreted unless --raw is set. GNU seq writes the separator only between
+// items, and ends the sequence with a newline.
+fn getseparators(opt: &Matches, compat: Compat) -> Result<Separators> {
+    let unescape = |s: String| {
+        if opt.opt_present("raw") {
+            Ok(s)
+        } else {
+            escape::unescape(&s)
+        }
+    };
+    let sep = unescape(opt.opt_str("s").unwrap_or_else(|| "\n".to_string()))?;
+    let term = opt.opt_str("t").map(unescape).transpose()?;
+    Ok(Separators {
+        sep,
+        term,
+        between: compat == Compat::Gnu,
+    })
+}
+
+// Returns the width to which to pad numbers: that set by --width, which may
+// be at most format::MAX_WIDTH, that of the widest number as determined by
+// widest if -w is set, or 1.
+fn getwidth(opt: &Matches, widest: impl FnOnce() -> Result<usize>) -> Result<usize> {
+    match opt.opt_str("width") {
+        Some(w) => match w.parse() {
+            Ok(width) if width <= format::MAX_WIDTH => Ok(width),
+            Ok(_) => Err(SeqError::InvalidArgument(format!(
+                "width greater than {}: {}",
+                format::MAX_WIDTH,
+                w
+            ))),
+            Err(_) => Err(SeqError::InvalidArgument(format!("invalid width: {}", w))),
+        },
+        None if opt.opt_present("w") => widest(),
+        None => Ok(1),
+    }
+}
+
+// Returns the character with which to pad numbers set by --pad.
+fn getpad(opt: &Matches) -> Result<Pad> {
+    match opt.opt_str("pad").as_deref() {
+        None | Some("zero") => Ok(Pad::Zero),
+        Some("space") => Ok(Pad::Space),
+        Some(p) => Err(SeqError::InvalidArgument(format!("invalid padding: {}", p))),
+    }
+}
+
+// Returns the conventions for writing decimal numbers: those of the C locale,
+// or of the locale set by --locale, with the thousands separator set by
+// --group and the decimal point set by --decimal-point. Digits are grouped
+// without --group only if group is set, as by the ' flag of -f, and then by
+// the thousands separator of the locale.
+fn getnumeric(opt: &Matches, group: bool) -> Result<Numeric> {
+    let numeric = match opt.opt_default("locale", "").as_deref() {
+        None => Numeric::default(),
+        Some("") => Numeric::env(),
+        Some(name) => Numeric::locale(name)
+            .ok_or_else(|| SeqError::InvalidArgument(format!("unknown locale: {}", name)))?,
+    };
+    let sep = numeric.separator().unwrap_or(",").to_string();
+    let numeric = match opt.opt_default("group", &sep) {
+        Some(sep) => numeric.group(&sep),
+        None if group => numeric,
+        None => numeric.ungroup(),
+    };
+    match opt.opt_str("decimal-point") {
+        None => Ok(numeric),
+        Some(point) => {
+            let mut chars = point.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if !c.is_ascii_digit() => Ok(numeric.point(c)),
+                _ => Err(SeqError::InvalidArgument(format!(
+                    "invalid decimal point: {}",
+                    point
+                ))),
+            }
+        }
+    }
+}
+
+// Returns the structured output format set by --output, if any.
+fn getoutput(opt: &Matches) -> Result<Option<Output>> {
+    let header = opt.opt_default("header", "value");
+    match opt.opt_str("output") {
+        Some(_) if opt.opt_present("s") || opt.opt_present("t") => Err(SeqError::Usage(Some(
+            "--output may not be specified with -s or -t".to_string(),
+        ))),
+        Some(name) => Ok(Some(Output::parse(&name, header)?)),
+        None if header.is_some() => Err(SeqError::Usage(Some(
+            "--header requires --output=csv".to_string(),
+        ))),
+        None => Ok(None),
+    }
+}
+
+fn getcompat(opt: &Matches) -> Result<Compat> {
+    match opt.opt_str("compat").as_deref() {
+        None | Some("crab") => Ok(Compat::Crab),
+        Some("gnu") => Ok(Compat::Gnu),
+        Some("bsd") => Ok(Compat::Bsd),
+        Some(c) => Err(SeqError::InvalidArgument(format!(
+            "invalid compatibility mode: {}",
+            c
+        ))),
+    }
+}
+
+// Moves operands after a "--" so that negative numbers such as -1 or -.5,
+// durations such as -1d, and range specs such as -1:1 are not mistaken for
+// options. Option arguments stay with their options; an option missing its
+// argument at the end of the line fails, rather than taking the "--".
+fn operands(opts: &Options, argv: &[String]) -> result::Result<Vec<String>, getopts::Fail> {
+    let mut args = Vec::with_capacity(argv.len() + 1);
+    let mut free = Vec::new();
+    let mut iter = argv.iter();
+
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            free.extend(iter.cloned());
+            break;
+        }
+        if arg.len() > 1
+            && arg.starts_with('-')
+            && !arg.split(':').all(|x| number::parse(x).is_some())
+            && Duration::parse(arg).is_none()
+        {
+            args.push(arg.clone());
+            if let Err(fail @ getopts::Fail::ArgumentMissing(_)) = opts.parse(&[arg]) {
+                match iter.next() {
+                    Some(val) => args.push(val.clone()),
+                    None => return Err(fail),
+                }
+            }
+        } else {
+            free.push(arg.clone());
+        }
+    }
+
+    args.push("--".into());
+    args.extend(free);
+    Ok(args)
+}
+
+macro_rules! float {
+    ($x:expr) => {
+        match number::parse(&$x) {
+            Some((x, _)) if x.is_nan() => return Err(SeqError::NotANumber($x.to_string())),
+            Some((x, _)) => x,
+            None => return Err(SeqError::InvalidNumber($x.to_string())),
+        }
+    };
+}
+
+fn getseq(args: &[String]) -> Result<SeqSpec> {
+    let (first, incr, last) = match args.len() {
+        1 => (1.0, None, float!(args[0])),
+        2 => (float!(args[0]), None, float!(args[1])),
+        3 => (float!(args[0]), Some(float!(args[1])), float!(args[2])),
+        _ => return Err(SeqError::Usage(None)),
+    };
+    Ok(SeqSpec {
+        first,
+        incr: increment(first, incr, last)?,
+        last,
+        precision: precision(args),
+        decimal: decimal(args),
+    })
+}
+
+// Returns the increment of a sequence from first to last: incr if it is set,
+// and otherwise 1, or -1 if first is greater than last. Fails if an operand
+// is NaN, or if incr is zero or runs away from last. Both getseq and Seq
+// check their operands with it.
+fn increment(first: f64, incr: Option<f64>, last: f64) -> Result<f64> {
+    if let Some(x) = [Some(first), incr, Some(last)]
+        .iter()
+        .flatten()
+        .find(|x| x.is_nan())
+    {
+        return Err(SeqError::NotANumber(x.to_string()));
+    }
+    match incr {
+        Some(0.0) => Err(SeqError::ZeroIncrement {
+            decrement: first >= last,
+        }),
+        Some(incr) if incr < 0.0 && first < last => {
+            Err(SeqError::WrongDirection { decrement: false })
+        }
+        Some(incr) if incr > 0.0 && first > last => {
+            Err(SeqError::WrongDirection { decrement: true })
+        }
+        Some(incr) => Ok(incr),
+        None if first > last => Ok(-1.0),
+        None => Ok(1.0),
+    }
+}
+
+// Reports whether a sequence without an increment is empty in GNU
+// compatibility mode, in which the default increment is 1 even when first is
+// larger than last, so that seq 1 0 prints nothing.
+fn gnuempty(compat: Compat, args: &[String], seq: &SeqSpec) -> bool {
+    compat == Compat::Gnu && args.len() < 3 && seq.first > seq.last
+}
+
+// Reports whether all of the operands are decimal literals, such as 10,
+// 0.25, or 1e-3, rather than hexadecimal numbers, inf, or nan.
+fn decimal(args: &[String]) -> bool {
+    args.iter().all(|x| {
+        number::ungroup(x)
+            .and_then(|x| Decimal::parse(&x))
+            .is_some()
+    })
+}
+
+// Returns the greatest number of decimal places among the operands, at which
+// the default format prints every item of the sequence.
+fn precision(args: &[String]) -> usize {
+    let mut precision = 0;
+    for num in args.iter().filter_map(|x| number::ungroup(x)) {
+        // Hexadecimal numbers need a decimal place for each binary place.
+        if num
+            .trim_start_matches(['-', '+'])
+            .get(..2)
+            .map(str::to_ascii_lowercase)
+            == Some("0x".into())
+        {
+            let places = number::parse(&num).map_or(0, |(x, _)| binplaces(x));
+            precision = cmp::max(precision, places);
+            continue;
+        }
+        // Count the digits after the decimal point less the exponent, so
+        // that 1.5e-3 has 4 places and 1.5e2 has none. Exponents too large
+        // to parse saturate.
+        let (mantissa, exp) = match num.find(['e', 'E']) {
+            Some(idx) => (&num[..idx], exponent(&num[idx + 1..])),
+            None => (&num[..], 0),
+        };
+        let places = mantissa
+            .find('.')
+            .map_or(0, |idx| mantissa.len() - (idx + 1));
+        // Keep the greater precision, to at most as many places as a
+        // decimal may have.
+        let places = (places as i64)
+            .saturating_sub(exp)
+            .clamp(0, MAX_DIGITS as i64);
+        precision = cmp::max(precision, places as usize);
+    }
+    precision
+}
+
+// Parses the exponent of a decimal number, saturating at the bounds of i64.
+fn exponent(s: &str) -> i64 {
+    s.parse().unwrap_or(if s.starts_with('-') {
+        i64::MIN
+    } else {
+        i64::MAX
+    })
+}
+
+// Returns the number of binary places after the point in x, which is also
+// the number of decimal places needed to print it exactly, as 2^-k has k
+// decimal places.
+fn binplaces(x: f64) -> usize {
+    let mut frac = if x.is_finite() { x.fract().abs() } else { 0.0 };
+    let mut places = 0;
+    while frac != 0.0 {
+        frac = (frac * 2.0).fract();
+        places += 1;
+    }
+    places
+}
+
+// Returns the number of digits after the decimal point needed to print every
+// item of a sequence in scientific notation. No item has digits below the
+// precision of the sequence or above the magnitude of first or last.
+fn sciplaces(seq: &SeqSpec) -> usize {
+    let max = seq.first.abs().max(seq.last.abs());
+    if max == 0.0 || !max.is_finite() {
+        return seq.precision;
+    }
+    let e = format!("{:e}", max);
+    let exp: i64 = e[e.find('e').unwrap() + 1..].parse().unwrap();
+    cmp::max(0, exp + seq.precision as i64) as usize
+}
+
+// Returns the number of decimal places needed to print x to at most 15
+// significant digits.
+fn places(x: f64) -> usize {
+    if x == 0.0 || !x.is_finite() {
+        return 0;
+    }
+    let e = format!("{:.14e}", x.abs());
+    let (mantissa, exp) = e.split_at(e.find('e').unwrap());
+    let exp: i32 = exp[1..].parse().unwrap();
+
+    // Count the significant digits, all but the first of which follow the
+    // decimal point in scientific notation.
+    let digits = mantissa.trim_end_matches('0').replace('.', "").len() as i32;
+    cmp::max(0, digits - 1 - exp) as usize
+}
+
+fn getcount(opt: &Matches) -> Result<Option<u64>> {
+    match opt.opt_str("count") {
+        None => Ok(None),
+        Some(n) => Ok(Some(n.parse().map_err(|_| {
+            SeqError::InvalidArgument(format!("invalid count: {}", n))
+        })?)),
+    }
+}
+
+// Returns a sequence of n items derived from first and last, or from first
+// and the increment set by --by. The last value of the returned sequence is
+// the value of its last item.
+fn getcountseq(args: &[String], n: u64, by: Option<&str>, exclude: bool) -> Result<SeqSpec> {
+    let step = match by {
+        Some(x) => Some(float!(x)),
+        None => None,
+    };
+    let (first, incr) = match (args.len(), step) {
+        (0, step) => (1.0, step.unwrap_or(1.0)),
+        (1, step) => (float!(args[0]), step.unwrap_or(1.0)),
+        (2, None) => {
+            let (first, last) = (float!(args[0]), float!(args[1]));
+            let steps = if exclude { n } else { n.saturating_sub(1) };
+            (
+                first,
+                if steps == 0 {
+                    0.0
+                } else {
+                    (last - first) / steps as f64
+                },
+            )
+        }
+        (2, Some(_)) => {
+            return Err(SeqError::Usage(Some(
+                "--by may not be specified with both first and last".to_string(),
+            )))
+        }
+        _ => return Err(SeqError::Usage(None)),
+    };
+
+    let mut precision = precision(args);
+    if let Some(by) = by {
+        precision = cmp::max(precision, self::precision(&[by.to_string()]));
+    }
+    Ok(SeqSpec {
+        first,
+        incr,
+        last: first + incr * n.saturating_sub(1) as f64,
+        precision: cmp::max(precision, places(incr)),
+        decimal: decimal(args) && by.is_none_or(|by| decimal(&[by.to_string()])),
+    })
+}
+
+// Parses an operand as an exact decimal if it is a decimal literal or an
+// integer with a radix prefix.
+fn exactnum(x: &str) -> Option<Decimal> {
+    match number::integer(x) {
+        Some(n) => Decimal::parse(&n.to_string()),
+        None => Decimal::parse(&number::ungroup(x)?),
+    }
+}
+
+// Returns the sequence as exact decimals when all of the operands are decimal
+// literals or integers, so that neither large integers nor long fractions
+// lose precision to floating point. Call only after getseq has validated the operands.
+fn getexact(args: &[String]) -> Option<Exact> {
+    let mut nums = args
+        .iter()
+        .map(|x| exactnum(x))
+        .collect::<Option<Vec<_>>>()?;
+    let last = nums.pop()?;
+    let first = if nums.is_empty() {
+        Decimal::parse("1")?
+    } else {
+        nums.remove(0)
+    };
+    let incr = match nums.pop() {
+        Some(incr) => incr,
+        None => Decimal::parse(if first <= last { "1" } else { "-1" })?,
+    };
+
+    // Give all three numbers the same scale to speed up arithmetic.
+    let scale = first.scale().max(incr.scale()).max(last.scale());
+    Some(SeqSpec {
+        first: first.rescale(scale),
+        incr: incr.rescale(scale),
+        last: last.rescale(scale),
+        precision: scale,
+        decimal: true,
+    })
+}
+
+// Returns a geometric sequence, in which incr is the ratio of each item to
+// the one before it. With a count of n items, derives the ratio from first
+// and last, spacing the items evenly on a logarithmic scale, or takes it from
+// --by (default 2). Otherwise, the sequence runs from first to last. If
+// logspace is true, first, last, and n are required.
+fn getgeoseq(
+    args: &[String],
+    count: Option<u64>,
+    by: Option<&str>,
+    exclude: bool,
+    logspace: bool,
+) -> Result<SeqSpec> {
+    if logspace && (count.is_none() || by.is_some() || args.len() != 2) {
+        return Err(SeqError::Usage(Some(
+            "--logspace requires -n, first, and last".to_string(),
+        )));
+    }
+
+    let seq = match count {
+        None => {
+            if args.len() != 3 {
+                return Err(SeqError::Usage(Some(
+                    "geometric sequence requires first, ratio, and last".to_string(),
+                )));
+            }
+            let s = SeqSpec::new(float!(args[0]), float!(args[1]), float!(args[2]));
+            if s.incr <= 0.0 {
+                return Err(SeqError::InvalidArgument(
+                    "ratio must be positive".to_string(),
+                ));
+            }
+            if s.incr == 1.0 {
+                return Err(SeqError::InvalidArgument("ratio must not be 1".to_string()));
+            }
+            if s.incr > 1.0 && s.last.abs() < s.first.abs() {
+                return Err(SeqError::InvalidArgument(
+                    "needs ratio less than 1".to_string(),
+                ));
+            }
+            if s.incr < 1.0 && s.last.abs() > s.first.abs() {
+                return Err(SeqError::InvalidArgument(
+                    "needs ratio greater than 1".to_string(),
+                ));
+            }
+            s
+        }
+        Some(n) => {
+            let (first, ratio) = match (args.len(), by) {
+                (0, by) => (1.0, by.map_or(Ok(2.0), ratio)?),
+                (1, by) => (float!(args[0]), by.map_or(Ok(2.0), ratio)?),
+                (2, None) => {
+                    let (first, last) = (float!(args[0]), float!(args[1]));
+                    let steps = if exclude { n } else { n.saturating_sub(1) };
+                    if first == 0.0 || first.signum() != last.signum() {
+                        return Err(SeqError::InvalidArgument(
+                            "first and last must be non-zero with the same sign".to_string(),
+                        ));
+                    }
+                    (
+                        first,
+                        if steps == 0 {
+                            1.0
+                        } else {
+                            (last / first).powf(1.0 / steps as f64)
+                        },
+                    )
+                }
+                (2, Some(_)) => {
+                    return Err(SeqError::Usage(Some(
+                        "--by may not be specified with both first and last".to_string(),
+                    )))
+                }
+                _ => return Err(SeqError::Usage(None)),
+            };
+            let s = SeqSpec::new(first, ratio, 0.0);
+            SeqSpec::new(first, ratio, geoat(&s, n.saturating_sub(1)))
+        }
+    };
+
+    if seq.first == 0.0 {
+        return Err(SeqError::InvalidArgument(
+            "geometric sequence must not start at zero".to_string(),
+        ));
+    }
+    if seq.incr <= 0.0 {
+        return Err(SeqError::InvalidArgument(
+            "ratio must be positive".to_string(),
+        ));
+    }
+    Ok(seq)
+}
+
+// Parses the ratio of a geometric sequence.
+fn ratio(arg: &str) -> Result<f64> {
+    Ok(float!(arg))
+}
+
+// Returns the item at index i of a geometric sequence.
+fn geoat(s: &SeqSpec, i: u64) -> f64 {
+    match i32::try_from(i) {
+        Ok(i) => s.first * s.incr.powi(i),
+        Err(_) => s.first * s.incr.powf(i as f64),
+    }
+}
+
+// Returns the number of items in a geometric sequence from first to last.
+fn geocount(s: &SeqSpec) -> Result<u64> {
+    count((s.last / s.first).ln() / s.incr.ln() + 1.0, |i| {
+        let x = geoat(s, i).abs();
+        if s.incr > 1.0 {
+            x <= s.last.abs()
+        } else {
+            x >= s.last.abs()
+        }
+    })
+}
+
+// Returns a sequence of dates from first to last in increments of incr
+// (default one day), and whether to print times: whether any operand has a
+// time or the increment is not a whole number of days. With a count of n
+// items, last is computed from first and an increment set by --by.
+fn getdateseq(args: &[String], count: Option<u64>, by: Option<&str>) -> Result<Dates> {
+    let date = |x: &String| {
+        DateTime::parse(x)
+            .ok_or_else(|| SeqError::InvalidArgument(format!("invalid date argument: {}", x)))
+    };
+    let duration = |x: &str| {
+        Duration::parse(x)
+            .ok_or_else(|| SeqError::InvalidArgument(format!("invalid duration argument: {}", x)))
+    };
+
+    let (first, incr, last) = match (count, args.len()) {
+        (Some(n), 1) => {
+            let first = date(&args[0])?;
+            let incr = by.map_or(Ok(Duration::DAY), duration)?;
+            let last = first
+                .0
+                .add(&incr, n.saturating_sub(1))
+                .ok_or_else(|| SeqError::InvalidArgument("date out of range".to_string()))?;
+            (first, incr, (last, false))
+        }
+        (Some(_), 2) if by.is_some() => {
+            return Err(SeqError::Usage(Some(
+                "--by may not be specified with both first and last".to_string(),
+            )))
+        }
+        (None, 2) => {
+            let (first, last) = (date(&args[0])?, date(&args[1])?);
+            let incr = if last.0 < first.0 {
+                Duration::parse("-1d").unwrap()
+            } else {
+                Duration::DAY
+            };
+            (first, incr, last)
+        }
+        (None, 3) => (date(&args[0])?, duration(&args[1])?, date(&args[2])?),
+        _ => return Err(SeqError::Usage(None)),
+    };
+
+    if incr.is_zero() {
+        return Err(SeqError::ZeroIncrement { decrement: false });
+    }
+    if incr.is_negative() && last.0 > first.0 {
+        return Err(SeqError::WrongDirection { decrement: false });
+    }
+    if !incr.is_negative() && last.0 < first.0 {
+        return Err(SeqError::WrongDirection { decrement: true });
+    }
+    Ok((
+        first.0,
+        incr,
+        last.0,
+        first.1 || last.1 || !incr.is_whole_days(),
+    ))
+}
+
+// Returns the number of dates in a sequence from first to last.
+fn datecount(s: &Dates) -> Result<u64> {
+    let est = (s.2.timestamp() - s.0.timestamp()) as f64 / s.1.approx_secs() + 1.0;
+    count(est, |i| match s.0.add(&s.1, i) {
+        Some(t) if s.1.is_negative() => t >= s.2,
+        Some(t) => t <= s.2,
+        None => false,
+    })
+}
+
+// Prints a sequence of dates with --date.
+fn emitdates(
+    out: &mut impl Write,
+    opt: &Matches,
+    count: Option<u64>,
+    by: Option<&str>,
+    order: Order,
+    seps: &Separators,
+) -> Result<()> {
+    exclusive(
+        opt,
+        "date",
+        &[
+            "radix",
+            "x",
+            "o",
+            "b",
+            "prefix",
+            "geometric",
+            "multiply",
+            "logspace",
+            "exclude-last",
+            "product",
+            "scientific",
+            "group",
+            "decimal-point",
+            "locale",
+            "w",
+            "width",
+            "pad",
+        ],
+    )?;
+
+    let dates = getdateseq(&opt.free, count, by)?;
+    let fmt = match opt.opt_str("f") {
+        Some(f) => DateFormat::parse(&f)?,
+        None if dates.3 => DateFormat::parse("%Y-%m-%dT%H:%M:%S")?,
+        None => DateFormat::parse("%Y-%m-%d")?,
+    };
+    let n = count.map_or_else(|| datecount(&dates), Ok)?;
+
+    // Every date up to the last one is within range, as dates only grow or
+    // only shrink.
+    emitorder(
+        out,
+        n,
+        order,
+        |i| fmt.render(&dates.0.add(&dates.1, i).unwrap()),
+        seps,
+        getoutput(opt)?.as_ref(),
+    )
+}
+
+// Returns the number of items in a column of a product and a function that
+// renders the item at an index, given the range spec of the column.
+fn getcolumn(opt: &Matches, compat: Compat, spec: &str) -> Result<Column> {
+    let args: Vec<String> = spec.split(':').map(String::from).collect();
+    let numeric = getnumeric(opt, false)?;
+    let pad = getpad(opt)?;
+    if args.len() > 3 {
+        return Err(SeqError::InvalidArgument(format!(
+            "invalid range: {}",
+            spec
+        )));
+    }
+    let seq = getseq(&args)?;
+    let ints = getints(&args, None, None);
+    let fmt = getformat(opt, compat, &args, &seq, ints.as_ref())?;
+    if gnuempty(compat, &args, &seq) {
+        return Ok((0, Box::new(|_| String::new())));
+    }
+
+    if let (Some(ints), Some(true)) = (ints, fmt.as_ref().map(Format::is_radix)) {
+        let fmt = fmt.unwrap().zero(pad == Pad::Zero);
+        let n = intcount(&ints)?;
+        return Ok((n, Box::new(move |i| fmt.render_int(intat(&ints, i)))));
+    }
+
+    if let (None, Some(exact)) = (&fmt, getexact(&args)) {
+        let width = getwidth(opt, || Ok(width!(exact, numeric numeric)))?;
+        let fmt = Format::fixed(width, exact.precision)
+            .numeric(&numeric)
+            .zero(pad == Pad::Zero);
+        let n = exactcount(&exact)?;
+        return Ok((
+            n,
+            Box::new(move |i| fmt.render_decimal(&exactat(&exact, i))),
+        ));
+    }
+
+    let fmt = match fmt {
+        Some(fmt) => fmt,
+        None if compat == Compat::Gnu => Format::parse("%g")?.numeric(&numeric),
+        None => Format::fixed(
+            getwidth(opt, || Ok(width!(seq, numeric numeric)))?,
+            seq.precision,
+        )
+        .numeric(&numeric),
+    }
+    .zero(pad == Pad::Zero);
+    let n = floatcount(&seq)?;
+    Ok((n, Box::new(move |i| fmt.render(floatat(&seq, i)))))
+}
+
+// Prints the cartesian product of the range specs with --product, varying
+// the last column fastest, as nested loops would. With --output, each tuple
+// is a record with one value per range.
+fn emitproduct(
+    out: &mut impl Write,
+    opt: &Matches,
+    compat: Compat,
+    order: Order,
+    seps: &Separators,
+) -> Result<()> {
+    exclusive(
+        opt,
+        "product",
+        &[
+            "geometric",
+            "multiply",
+            "logspace",
+            "n",
+            "by",
+            "exclude-last",
+        ],
+    )?;
+    if opt.free.is_empty() {
+        return Err(SeqError::Usage(None));
+    }
+
+    let columns = opt
+        .free
+        .iter()
+        .map(|spec| getcolumn(opt, compat, spec))
+        .collect::<Result<Vec<Column>>>()?;
+    let n = columns
+        .iter()
+        .try_fold(1u64, |n, c| n.checked_mul(c.0))
+        .ok_or(SeqError::TooLong)?;
+    let tuple = |mut i| {
+        let mut fields = vec![String::new(); columns.len()];
+        for (field, col) in fields.iter_mut().zip(&columns).rev() {
+            *field = (col.1)(i % col.0);
+            i /= col.0;
+        }
+        fields
+    };
+
+    if let Some(output) = getoutput(opt)? {
+        if opt.opt_present("d") {
+            return Err(SeqError::Usage(Some(
+                "--output may not be specified with -d".to_string(),
+            )));
+        }
+        output.begin_records(out, columns.len())?;
+        for (idx, i) in (0..).zip(indexes(n, order)) {
+            output.record(out, idx, &tuple(i))?;
+        }
+        output.end(out)?;
+        return Ok(());
+    }
+
+    let delim = opt.opt_str("d").unwrap_or_else(|| " ".to_string());
+    emitorder(out, n, order, |i| tuple(i).join(&delim), seps, None)
+}
+
+// Returns an error if any of the options is set along with the option of a
+// mode.
+fn exclusive(opt: &Matches, mode: &str, names: &[&str]) -> Result<()> {
+    for name in names {
+        if opt.opt_present(name) {
+            let dash = if name.len() == 1 { "-" } else { "--" };
+            return Err(SeqError::Usage(Some(format!(
+                "--{} may not be specified with {}{}",
+                mode, dash, name
+            ))));
+        }
+    }
+    Ok(())
+}
+
+// Returns the exact form of a sequence of n items derived from first and the
+// increment set by --by, when both are decimal literals or integers. The
+// last value of the returned sequence is the value of its last item.
+fn getexactcount(args: &[String], n: u64, by: Option<&str>) -> Option<Exact> {
+    let first = match args.len() {
+        0 => Decimal::parse("1")?,
+        1 => exactnum(&args[0])?,
+        _ => return None,
+    };
+    let incr = exactnum(by.unwrap_or("1"))?;
+
+    let scale = cmp::max(first.scale(), incr.scale());
+    let (first, incr) = (first.rescale(scale), incr.rescale(scale));
+    let mut last = incr.mul(n.saturating_sub(1));
+    last.add(&first);
+    Some(SeqSpec {
+        first,
+        incr,
+        last,
+        precision: scale,
+        decimal: true,
+    })
+}
+
+// Returns the sequence as integers when all of the operands are integers
+// within the range of i128, written in decimal digits or with a radix prefix.
+// With a count of n items, derives the sequence from first and the increment
+// set by --by. Call only after getseq or getcountseq has validated the
+// operands.
+fn getints(args: &[String], count: Option<u64>, by: Option<&str>) -> Option<Ints> {
+    let mut nums = args
+        .iter()
+        .map(|x| number::integer(x))
+        .collect::<Option<Vec<_>>>()?;
+    if let Some(n) = count {
+        let first = match nums[..] {
+            [] => 1,
+            [first] => first,
+            _ => return None,
+        };
+        let incr = number::integer(by.unwrap_or("1"))?;
+        let last = incr
+            .checked_mul(i128::from(n.saturating_sub(1)))?
+            .checked_add(first)?;
+        return Some(SeqSpec::new(first, incr, last));
+    }
+
+    let last = nums.pop()?;
+    let first = if nums.is_empty() { 1 } else { nums.remove(0) };
+    let incr = nums.pop().unwrap_or(if first <= last { 1 } else { -1 });
+    Some(SeqSpec::new(first, incr, last))
+}
+
+// Returns the item at index i of an integer sequence.
+fn intat(s: &Ints, i: u64) -> i128 {
+    // The item lies between first and last, so the wrapped result is exact.
+    s.first.wrapping_add(s.incr.wrapping_mul(i128::from(i)))
+}
+
+// Returns the number of items in an integer sequence.
+fn intcount(s: &Ints) -> Result<u64> {
+    if s.incr < 0 && s.first < s.last || s.incr > 0 && s.first > s.last {
+        return Ok(0);
+    }
+    let n = s.last.abs_diff(s.first) / s.incr.unsigned_abs();
+    u64::try_from(n)
+        .ok()
+        .and_then(|n| n.checked_add(1))
+        .ok_or(SeqError::TooLong)
+}
+
+// Returns the item at index i of an exact sequence.
+fn exactat(s: &Exact, i: u64) -> Decimal {
+    let mut x = s.incr.mul(i);
+    x.add(&s.first);
+    x
+}
+
+// Returns the number of items emitseq emits for a sequence.
+fn floatcount(s: &SeqSpec) -> Result<u64> {
+    count(((s.last - s.first) / s.incr).floor() + 1.0, |i| {
+        let x = floatat(s, i);
+        if s.incr < 0.0 {
+            x >= s.last
+        } else {
+            x <= s.last
+        }
+    })
+}
+
+// Returns the item at an index in a sequence. When the operands are decimal
+// numbers, an item within rounding error of a number at the precision of the
+// sequence is rounded to it, so that the error in first + incr * i neither
+// carries the item past last, as in seq 0 0.1 0.3, nor shows up in its
+// output. Items of other sequences, such as those with hexadecimal operands,
+// are left as they are.
+fn floatat(s: &SeqSpec, i: u64) -> f64 {
+    let step = s.incr * i as f64;
+    let x = s.first + step;
+    if !s.decimal || s.precision > 22 {
+        return x;
+    }
+    // The error in first + incr * i, and in scaling it, is at most a few
+    // ULPs of the larger of first and step.
+    let scale = 10f64.powi(s.precision as i32);
+    let ulp = (s.first.abs() + step.abs()) * scale * f64::EPSILON;
+    let (n, err) = (x * scale, 4.0 * ulp);
+    if n.abs() < 9007199254740992.0 && (n - n.round()).abs() <= err {
+        n.round() / scale
+    } else {
+        x
+    }
+}
+
+// Returns the number of items emitexact emits for a sequence.
+fn exactcount(s: &Exact) -> Result<u64> {
+    let (first, incr, last) = (s.first.to_f64(), s.incr.to_f64(), s.last.to_f64());
+    count(((last - first) / incr).floor() + 1.0, |i| {
+        let x = exactat(s, i);
+        if s.incr.is_negative() {
+            x >= s.last
+        } else {
+            x <= s.last
+        }
+    })
+}
+
+// Corrects an estimated number of items in a sequence, where within reports
+// whether the item at an index falls within the sequence.
+fn count(est: f64, within: impl Fn(u64) -> bool) -> Result<u64> {
+    if !est.is_finite() || est >= u64::MAX as f64 {
+        return Err(SeqError::TooLong);
+    }
+    let mut n = est.max(0.0) as u64;
+    while n > 0 && !within(n - 1) {
+        n -= 1;
+    }
+    while within(n) {
+        n += 1;
+    }
+    Ok(n)
+}
+
+fn emitseq(out: &mut impl Write, s: &SeqSpec, fmt: &Format, seps: &Separators) -> Result<()> {
+    let mut cur = s.first;
+    let mut iter = 0u64;
+
+    while if s.incr < 0.0 {
+        cur >= s.last
+    } else {
+        cur <= s.last
+    } {
+        write!(
+            out,
+            "{}{}{}",
+            seps.before(iter),
+            fmt.render(cur),
+            seps.after()
+        )?;
+        iter = iter.checked_add(1).ok_or(SeqError::TooLong)?;
+        cur = floatat(s, iter);
+    }
+
+    write!(out, "{}", seps.end(iter))?;
+    Ok(())
+}
+
+fn emitexact(out: &mut impl Write, s: &Exact, fmt: &Format, seps: &Separators) -> Result<()> {
+    let mut cur = s.first.clone();
+    let mut n = 0;
+
+    while if s.incr.is_negative() {
+        cur >= s.last
+    } else {
+        cur <= s.last
+    } {
+        write!(
+            out,
+            "{}{}{}",
+            seps.before(n),
+            fmt.render_decimal(&cur),
+            seps.after()
+        )?;
+        cur.add(&s.incr);
+        n += 1;
+    }
+
+    write!(out, "{}", seps.end(n))?;
+    Ok(())
+}
+
+// Emits the n items of a sequence in the order, rendering each item by its
+// index, in the structured output format if any.
+fn emitorder(
+    out: &mut impl Write,
+    n: u64,
+    order: Order,
+    render: impl Fn(u64) -> String,
+    seps: &Separators,
+    output: Option<&Output>,
+) -> Result<()> {
+    emititems(out, indexes(n, order).map(render), seps, output)
+}
+
+// Returns the indexes of the n items of a sequence in the order.
+fn indexes(n: u64, order: Order) -> Box<dyn Iterator<Item = u64>> {
+    match order {
+        Order::Forward => Box::new(0..n),
+        Order::Reverse => Box::new((0..n).rev()),
+        Order::Shuffle(seed) => Box::new(Permutation::new(n, seed)),
+    }
+}
+
+// Emits rendered items as they come, in the structured output format if any.
+fn emititems(
+    out: &mut impl Write,
+    items: impl Iterator<Item = String>,
+    seps: &Separators,
+    output: Option<&Output>,
+) -> Result<()> {
+    if let Some(output) = output {
+        output.begin(out)?;
+        for (idx, item) in (0..).zip(items) {
+            output.item(out, idx, &item)?;
+        }
+        output.end(out)?;
+        return Ok(());
+    }
+
+    let mut n = 0;
+    for item in items {
+        write!(out, "{}{}{}", seps.before(n), item, seps.after())?;
+        n += 1;
+    }
+
+    write!(out, "{}", seps.end(n))?;
+    Ok(())
+}
+
+// Emits a sequence of non-negative integers written as decimal digits. Adds
+// the increment to the digits of the current number in place and collects
+// the output in a reusable buffer, which is much faster than formatting each
+// number.
+fn emitints(
+    out: &mut impl Write,
+    first: &str,
+    incr: &str,
+    last: &str,
+    width: usize,
+    seps: &Separators,
+) -> Result<()> {
+    let (incr, last) = (incr.as_bytes(), last.as_bytes());
+    let (sep, after) = (seps.sep.as_bytes(), seps.after().as_bytes());
+
+    // The sum of two numbers has at most one more digit than the larger.
+    let size = first.len().max(incr.len()).max(last.len()) + 1;
+    let mut num = vec![b'0'; size];
+    let mut start = size - first.len();
+    num[start..].copy_from_slice(first.as_bytes());
+    let mut buf = Vec::with_capacity(BUFSIZE + size.max(width) + 2 * sep.len());
+    let mut n = 0;
+
+    while (size - start, &num[start..]) <= (last.len(), last) {
+        buf.extend_from_slice(seps.before(n).as_bytes());
+        buf.resize(buf.len() + width.saturating_sub(size - start), b'0');
+        buf.extend_from_slice(&num[start..]);
+        buf.extend_from_slice(after);
+        n += 1;
+        if buf.len() >= BUFSIZE {
+            out.write_all(&buf)?;
+            buf.clear();
+        }
+
+        let mut idx = size;
+        let mut carry = 0;
+        for digit in incr.iter().rev() {
+            idx -= 1;
+            let sum = num[idx] - b'0' + digit - b'0' + carry;
+            num[idx] = b'0' + sum % 10;
+            carry = sum / 10;
+        }
+        while carry > 0 {
+            idx -= 1;
+            if num[idx] == b'9' {
+                num[idx] = b'0';
+            } else {
+                num[idx] += 1;
+                carry = 0;
+            }
+        }
+        start = start.min(idx);
+    }
+
+    buf.extend_from_slice(seps.end(n).as_bytes());
+    out.write_all(&buf)?;
+    Ok(())
+}
+
+#[path = "seq_test.rs"]
+#[cfg(test)]
+mod test;
@@ -0,0 +1,3409 @@
+use super::*;
+
+// Returns the sequence from first to last in increments of incr.
+fn spec(first: f64, incr: f64, last: f64, precision: usize) -> SeqSpec {
+    SeqSpec {
+        first,
+        incr,
+        last,
+        precision,
+        decimal: true,
+    }
+}
+
+// Returns the separators written after each item.
+fn seps(sep: &str, term: Option<&str>) -> Separators {
+    Separators {
+        sep: sep.into(),
+        term: term.map(String::from),
+        between: false,
+    }
+}
+
+// Runs seq with the arguments and returns its output.
+fn runseq(args: &[&str]) -> Result<String> {
+    let args: Vec<String> = args.iter().map(|x| x.to_string()).collect();
+    let mut buf: Vec<u8> = vec![];
+    run(&mut buf, &args)?;
+    Ok(String::from_utf8(buf).unwrap())
+}
+
+#[test]
+fn test_usage() {
+    assert_eq!(
+        usage!(),
+        "Usage: seq [-w] [-f format] [-s string] [-t string] [first [incr]] last",
+    );
+}
+
+#[test]
+fn test_width() {
+    struct TestCase<'a> {
+        desc: &'a str,
+        seq: SeqSpec,
+        exp: usize,
+    }
+    for item in [
+        TestCase {
+            desc: "one",
+            seq: spec(1.0, 1.0, 1.0, 0),
+            exp: 1,
+        },
+        TestCase {
+            desc: "two",
+            seq: spec(1.0, 1.0, 10.0, 0),
+            exp: 2,
+        },
+        TestCase {
+            desc: "three",
+            seq: spec(100.0, 1.0, 10.0, 0),
+            exp: 3,
+        },
+        TestCase {
+            desc: "one.one",
+            seq: spec(1.0, 1.0, 1.0, 1),
+            exp: 3,
+        },
+        TestCase {
+            desc: "one.two",
+            seq: spec(1.0, 1.0, 1.0, 2),
+            exp: 4,
+        },
+        TestCase {
+            desc: "two.two",
+            seq: spec(1.0, 10.0, 1.0, 2),
+            exp: 4,
+        },
+    ] {
+        assert_eq!(
+            width!(item.seq),
+            item.exp,
+            "Failed testing width {}",
+            item.desc
+        );
+    }
+}
+
+#[test]
+fn test_options() -> Result<()> {
+    struct TestCase<'a> {
+        desc: String,
+        args: Vec<&'a str>,
+        unset: Vec<&'a str>,
+        free: Vec<&'a str>,
+        set: Vec<&'a str>,
+        vals: Vec<Vec<&'a str>>,
+    }
+
+    let defined_opts = vec![
+        "w",
+        "f",
+        "s",
+        "t",
+        "raw",
+        "compat",
+        "radix",
+        "x",
+        "o",
+        "b",
+        "prefix",
+        "scientific",
+        "reverse",
+        "shuffle",
+        "count",
+        "by",
+        "exclude-last",
+        "geometric",
+        "multiply",
+        "logspace",
+        "date",
+        "output",
+        "header",
+        "product",
+        "d",
+        "group",
+        "decimal-point",
+        "locale",
+        "width",
+        "pad",
+        "help",
+        "version",
+    ];
+    let opts = options();
+    for item in [
+        TestCase {
+            desc: "no args".into(),
+            args: Vec::new(),
+            unset: defined_opts.clone(),
+            free: Vec::new(),
+            set: Vec::new(),
+            vals: Vec::new(),
+        },
+        TestCase {
+            desc: "one free".into(),
+            args: vec!["10"],
+            unset: defined_opts.clone(),
+            free: vec!["10"],
+            set: Vec::new(),
+            vals: Vec::new(),
+        },
+        TestCase {
+            desc: "w and one free".into(),
+            args: vec!["-w", "8"],
+            unset: vec!["f", "s", "t", "compat"],
+            set: vec!["w"],
+            free: vec!["8"],
+            vals: Vec::new(),
+        },
+        TestCase {
+            desc: "w and two free".into(),
+            args: vec!["-w", "8", "10"],
+            unset: vec!["f", "s", "t", "compat"],
+            set: vec!["w"],
+            free: vec!["8", "10"],
+            vals: Vec::new(),
+        },
+        TestCase {
+            desc: "negated arg".into(),
+            args: vec!["-w", "--", "-8", "10"],
+            unset: vec!["f", "s", "t", "compat"],
+            set: vec!["w"],
+            free: vec!["-8", "10"],
+            vals: Vec::new(),
+        },
+        TestCase {
+            desc: "all options".into(),
+            args: vec!["-w", "-t", "foo", "-s", "^", "-f", "%g", "10"],
+            unset: vec!["compat"],
+            set: vec!["w", "t", "s", "f"],
+            free: vec!["10"],
+            vals: vec![vec!["t", "foo"], vec!["s", "^"], vec!["f", "%g"]],
+        },
+        TestCase {
+            desc: "long options".into(),
+            args: vec![
+                "--equal-width",
+                "--terminator",
+                "🤖",
+                "--separator",
+                ":",
+                "--format",
+                "%.2e",
+                "--compat=bsd",
+                "--",
+                "10",
+                "-5",
+            ],
+            unset: Vec::new(),
+            set: vec!["equal-width", "terminator", "separator", "format", "compat"],
+            free: vec!["10", "-5"],
+            vals: vec![
+                vec!["t", "🤖"],
+                vec!["s", ":"],
+                vec!["f", "%.2e"],
+                vec!["compat", "bsd"],
+            ],
+        },
+    ] {
+        let matches = opts.parse(item.args)?;
+        // Make sure all options are defined.
+        for opt in &defined_opts {
+            assert!(matches.opt_defined(opt), "Option -{} not defined", opt);
+        }
+
+        // Check for unset args.
+        for opt in &item.unset {
+            assert!(
+                !matches.opt_present(opt),
+                "Option -{} should not be present with {}",
+                opt,
+                item.desc,
+            );
+        }
+
+        // Check for presence of set args.
+        for opt in &item.set {
+            assert!(
+                matches.opt_present(opt),
+                "Option -{} should not be present with {}",
+                opt,
+                item.desc,
+            );
+        }
+
+        // Check for option values.
+        for kv in &item.vals {
+            assert_eq!(
+                matches.opt_str(kv[0]),
+                Some(kv[1].into()),
+                "Missing -{} string with {}",
+                kv[0],
+                item.desc,
+            );
+        }
+
+        // Check for free args.
+        assert_eq!(
+            matches.free, item.free,
+            "Invalid free strings with {}",
+            item.desc
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bad_options() {
+    let opts = options();
+    match opts.parse(vec!["-z"]) {
+        Err(e) => assert_eq!(e.to_string(), "Unrecognized option: \'z\'"),
+        Ok(_) => panic!("Option -z should be invalid"),
+    }
+    match opts.parse(vec!["--foo"]) {
+        Err(e) => assert_eq!(e.to_string(), "Unrecognized option: \'foo\'"),
+        Ok(_) => panic!("Option -foo should be invalid"),
+    }
+    for (args, exp) in [
+        (vec!["-t"], "Argument to option 't' missing"),
+        (vec!["1", "3", "-s"], "Argument to option 's' missing"),
+        (vec!["-w", "1", "-ws"], "Argument to option 's' missing"),
+        (vec!["-n"], "Argument to option 'n' missing"),
+        (
+            vec!["1", "--separator"],
+            "Argument to option 'separator' missing",
+        ),
+    ] {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        match operands(&opts, &args) {
+            Err(e) => assert_eq!(e.to_string(), exp, "Should fail for {:?}", args),
+            Ok(res) => panic!("Operands {:?} should be invalid, got {:?}", args, res),
+        }
+    }
+}
+
+#[test]
+fn test_operands() {
+    let opts = options();
+    for (args, exp) in [
+        (vec![], vec!["--"]),
+        (vec!["10"], vec!["--", "10"]),
+        (vec!["-1"], vec!["--", "-1"]),
+        (vec!["-w", "-1", "1"], vec!["-w", "--", "-1", "1"]),
+        (vec!["-.5", "-1e3"], vec!["--", "-.5", "-1e3"]),
+        (vec!["-inf", "-5"], vec!["--", "-inf", "-5"]),
+        (vec!["-s", "-1", "-2"], vec!["-s", "-1", "--", "-2"]),
+        (vec!["-s-1", "-2"], vec!["-s-1", "--", "-2"]),
+        (vec!["-ws", "-", "-2"], vec!["-ws", "-", "--", "-2"]),
+        (
+            vec!["--separator", "-", "-2"],
+            vec!["--separator", "-", "--", "-2"],
+        ),
+        (
+            vec!["--separator=-", "-2"],
+            vec!["--separator=-", "--", "-2"],
+        ),
+        (vec!["-1", "-w", "1"], vec!["-w", "--", "-1", "1"]),
+        (vec!["-z", "-1"], vec!["-z", "--", "-1"]),
+        (vec!["-", "-1"], vec!["--", "-", "-1"]),
+        (vec!["-1", "--", "-w"], vec!["--", "-1", "-w"]),
+        (vec!["-t", "-"], vec!["-t", "-", "--"]),
+        (vec!["1", "-s", "--"], vec!["-s", "--", "--", "1"]),
+    ] {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        assert_eq!(
+            operands(&opts, &args).unwrap(),
+            exp,
+            "Should get expected operands for {:?}",
+            args,
+        );
+    }
+}
+
+#[test]
+fn test_getradix() -> Result<()> {
+    let opts = options();
+    for (args, exp) in [
+        (vec![], None),
+        (vec!["--radix=10"], Some(10)),
+        (vec!["--radix", "16"], Some(16)),
+        (vec!["--radix=8"], Some(8)),
+        (vec!["--radix=2"], Some(2)),
+        (vec!["-x"], Some(16)),
+        (vec!["--octal"], Some(8)),
+        (vec!["-b"], Some(2)),
+        (vec!["-x", "--radix=16"], Some(16)),
+    ] {
+        assert_eq!(
+            getradix(&opts.parse(&args)?)?,
+            exp,
+            "Should get radix for {:?}",
+            args,
+        );
+    }
+
+    for (args, err) in [
+        (vec!["--radix=3"], "seq: invalid radix: 3"),
+        (vec!["--radix=hex"], "seq: invalid radix: hex"),
+        (vec!["-x", "-o"], "seq: only one radix may be specified"),
+        (
+            vec!["--radix=2", "-x"],
+            "seq: only one radix may be specified",
+        ),
+    ] {
+        match getradix(&opts.parse(&args)?) {
+            Err(e) => assert_eq!(e.to_string(), err, "Should get error for {:?}", args),
+            Ok(_) => panic!("Should get error for {:?}", args),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_detectradix() {
+    for (args, exp) in [
+        (vec!["10"], None),
+        (vec!["1", "10"], None),
+        (vec!["0x10"], Some(16)),
+        (vec!["0x10", "0x20"], Some(16)),
+        (vec!["1", "0x10"], Some(16)),
+        (vec!["-0o10", "2", "0o20"], Some(8)),
+        (vec!["0b1", "0b1000"], Some(2)),
+        (vec!["0x10", "0o20"], None),
+        (vec!["0x10", ".5", "0x20"], None),
+        (vec!["0x1p4"], None),
+        (vec!["0x10", "inf"], None),
+        (vec!["0x10", "x"], None),
+    ] {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        assert_eq!(detectradix(&args), exp, "Should detect radix of {:?}", args);
+    }
+}
+
+#[test]
+fn test_getorder() -> Result<()> {
+    let opts = options();
+    for (args, exp) in [
+        (vec![], Order::Forward),
+        (vec!["--reverse"], Order::Reverse),
+        (vec!["--shuffle=42"], Order::Shuffle(42)),
+        (vec!["--shuffle=0"], Order::Shuffle(0)),
+    ] {
+        assert_eq!(
+            getorder(&opts.parse(&args)?)?,
+            exp,
+            "Should get order for {:?}",
+            args,
+        );
+    }
+    match getorder(&opts.parse(["--shuffle"])?)? {
+        Order::Shuffle(_) => (),
+        order => panic!("Should get shuffle order, got {:?}", order),
+    }
+
+    for (args, err) in [
+        (
+            vec!["--reverse", "--shuffle"],
+            "seq: cannot both reverse and shuffle",
+        ),
+        (vec!["--shuffle=x"], "seq: invalid shuffle seed: x"),
+        (vec!["--shuffle=-1"], "seq: invalid shuffle seed: -1"),
+    ] {
+        match getorder(&opts.parse(&args)?) {
+            Err(e) => assert_eq!(e.to_string(), err, "Should get error for {:?}", args),
+            Ok(_) => panic!("Should get error for {:?}", args),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_count() -> Result<()> {
+    for (args, exp) in [
+        (vec!["1"], 1),
+        (vec!["10"], 10),
+        (vec!["0"], 2),
+        (vec!["5", "5"], 1),
+        (vec!["1", "3", "10"], 4),
+        (vec!["10", "-3", "1"], 4),
+        (vec!["1", "0.3", "2.1"], 4),
+        (vec!["0", "0.1", "1"], 11),
+        (vec!["-1", "-0.3", "-2.1"], 4),
+        (vec!["1", "100", "5"], 1),
+        (vec!["18446744073709551610", "18446744073709551620"], 11),
+    ] {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        let seq = getseq(&args)?;
+        let mut buf: Vec<u8> = vec![];
+        emitseq(
+            &mut buf,
+            &seq,
+            &Format::fixed(1, seq.precision),
+            &seps("\n", None),
+        )?;
+        assert_eq!(
+            floatcount(&seq)?,
+            buf.iter().filter(|c| **c == b'\n').count() as u64,
+            "Float count should match emitseq for {:?}",
+            args
+        );
+
+        let exact = getexact(&args).unwrap();
+        assert_eq!(exactcount(&exact)?, exp, "Should count {:?}", args);
+    }
+
+    let seq = getseq(&["1".to_string(), "inf".to_string()])?;
+    match floatcount(&seq) {
+        Err(e) => assert_eq!(e.to_string(), "seq: sequence too long"),
+        Ok(n) => panic!("Should get error for infinite count, got {}", n),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_places() {
+    for (num, exp) in [
+        (0.0, 0),
+        (1.0, 0),
+        (-2.0, 0),
+        (100.0, 0),
+        (0.5, 1),
+        (0.25, 2),
+        (-0.125, 3),
+        (2.5, 1),
+        (1.0 / 3.0, 15),
+        (100.0 / 3.0, 13),
+        (1e-20, 20),
+        (1.5e20, 0),
+        (f64::INFINITY, 0),
+    ] {
+        assert_eq!(places(num), exp, "Should get places for {}", num);
+    }
+}
+
+#[test]
+fn test_precision() {
+    for (args, exp) in [
+        (vec!["1", "2"], 0),
+        (vec!["1", "0.25", "2"], 2),
+        (vec!["1e-3"], 3),
+        (vec!["1.5e-3"], 4),
+        (vec!["1E-3", "0.5"], 3),
+        (vec!["1.5e2"], 0),
+        (vec!["1.25e1"], 1),
+        (vec!["1.5e+2", "0.5"], 1),
+        (vec!["0x1.8p1"], 0),
+        (vec!["0x1p-3", "1"], 3),
+        (vec!["-0x.1", "0x10"], 4),
+        (vec!["0x1p-1022"], 1022),
+        (vec!["1_000.5"], 1),
+        (vec!["inf"], 0),
+        (vec!["1e-70000", "1"], 4096),
+        (vec!["1e-9223372036854775808", "1"], 4096),
+        (vec!["1e-99999999999999999999", "1"], 4096),
+        (vec!["1.5e9223372036854775807"], 0),
+        (vec!["1.5e99999999999999999999"], 0),
+    ] {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        assert_eq!(precision(&args), exp, "Should get precision of {:?}", args);
+    }
+
+    // Sequences with tiny operands print at the greatest precision.
+    for first in ["1e-70000", "1e-9223372036854775808"] {
+        let zeros = "0".repeat(4096);
+        assert!(
+            runseq(&["-s,", first, "1"]).unwrap() == format!("0.{0},1.{0},", zeros),
+            "Should print {} at 4096 places",
+            first
+        );
+    }
+}
+
+#[test]
+fn test_sciplaces() {
+    for (seq, exp) in [
+        (spec(1e-3, 1e-3, 5e-3, 3), 0),
+        (spec(1e-3, 1e-3, 1e-2, 3), 1),
+        (spec(150.0, 1.0, 152.0, 0), 2),
+        (spec(1.0, 0.5, 3.0, 1), 1),
+        (spec(-1000.0, 500.0, 1000.0, 0), 3),
+        (spec(0.0, 1.0, 0.0, 0), 0),
+        (spec(1.0, 1.0, f64::INFINITY, 0), 0),
+    ] {
+        assert_eq!(sciplaces(&seq), exp, "Should get places of {:?}", seq);
+    }
+}
+
+#[test]
+fn test_getcountseq() -> Result<()> {
+    for (args, n, by, exclude, exp) in [
+        (vec![], 5, None, false, spec(1.0, 1.0, 5.0, 0)),
+        (vec!["10"], 3, None, false, spec(10.0, 1.0, 12.0, 0)),
+        (vec!["10"], 3, Some("-2.5"), false, spec(10.0, -2.5, 5.0, 1)),
+        (vec![], 3, Some("0"), false, spec(1.0, 0.0, 1.0, 0)),
+        (vec!["0", "1"], 5, None, false, spec(0.0, 0.25, 1.0, 2)),
+        (vec!["0", "1"], 4, None, true, spec(0.0, 0.25, 0.75, 2)),
+        (vec!["1", "0"], 3, None, false, spec(1.0, -0.5, 0.0, 1)),
+        (vec!["0.00", "1"], 3, None, false, spec(0.0, 0.5, 1.0, 2)),
+        (vec!["2", "4"], 1, None, false, spec(2.0, 0.0, 2.0, 0)),
+        (vec!["2", "4"], 0, None, false, spec(2.0, 0.0, 2.0, 0)),
+    ] {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        assert_eq!(
+            getcountseq(&args, n, by, exclude)?,
+            exp,
+            "Should get sequence for {:?} count {}",
+            args,
+            n
+        );
+    }
+
+    for (args, by, err) in [
+        (
+            vec!["1", "2"],
+            Some("1"),
+            "seq: --by may not be specified with both first and last",
+        ),
+        (vec!["1", "2", "3"], None, usage!()),
+        (vec!["x"], None, "seq: invalid floating point argument: x"),
+        (vec![], Some("y"), "seq: invalid floating point argument: y"),
+        (
+            vec![],
+            Some("nan"),
+            "seq: invalid not-a-number argument: nan",
+        ),
+    ] {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        match getcountseq(&args, 3, by, false) {
+            Err(e) => assert_eq!(e.to_string(), err, "Should get error for {:?}", args),
+            Ok(_) => panic!("Should get error for {:?}", args),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_getseq() {
+    struct TestCase {
+        desc: String,
+        args: Vec<String>,
+        seq: SeqSpec,
+    }
+
+    for item in vec![
+        TestCase {
+            desc: "arg 10".into(),
+            args: vec!["10".into()],
+            seq: spec(1f64, 1f64, 10f64, 0usize),
+        },
+        TestCase {
+            desc: "args 10, 20".into(),
+            args: vec!["10".into(), "20".into()],
+            seq: spec(10f64, 1f64, 20f64, 0usize),
+        },
+        TestCase {
+            desc: "args 10, 2, 20".into(),
+            args: vec!["10".into(), "2".into(), "20".into()],
+            seq: spec(10f64, 2f64, 20f64, 0usize),
+        },
+        TestCase {
+            desc: "args -10, 5, 10".into(),
+            args: vec!["-10".into(), "5".into(), "10".into()],
+            seq: spec(-10f64, 5f64, 10f64, 0usize),
+        },
+        TestCase {
+            desc: "args -10, 5, 0".into(),
+            args: vec!["-10".into(), "5".into(), "0".into()],
+            seq: spec(-10f64, 5f64, 0f64, 0usize),
+        },
+        TestCase {
+            desc: "args -10, 2, -6".into(),
+            args: vec!["-10".into(), "2".into(), "-6".into()],
+            seq: spec(-10f64, 2f64, -6f64, 0usize),
+        },
+        TestCase {
+            desc: "args 10, -2, -6".into(),
+            args: vec!["10".into(), "-2".into(), "-6".into()],
+            seq: spec(10f64, -2f64, -6f64, 0usize),
+        },
+        TestCase {
+            desc: "args 10, -6".into(),
+            args: vec!["10".into(), "-6".into()],
+            seq: spec(10f64, -1f64, -6f64, 0usize),
+        },
+        TestCase {
+            desc: "args 10.0, 2.25".into(),
+            args: vec!["10.0".into(), "2.25".into()],
+            seq: spec(10f64, -1f64, 2.25f64, 2usize),
+        },
+        TestCase {
+            desc: "args 10.4".into(),
+            args: vec!["10.4".into()],
+            seq: spec(1f64, 1f64, 10.4f64, 1usize),
+        },
+        TestCase {
+            desc: "args 10.225".into(),
+            args: vec!["10.225".into()],
+            seq: spec(1f64, 1f64, 10.225f64, 3usize),
+        },
+        TestCase {
+            desc: "args 0x10, 0x20".into(),
+            args: vec!["0x10".into(), "0x20".into()],
+            seq: SeqSpec {
+                decimal: false,
+                ..spec(16f64, 1f64, 32f64, 0usize)
+            },
+        },
+        TestCase {
+            desc: "args 0x1.8p1".into(),
+            args: vec!["0x1.8p1".into()],
+            seq: SeqSpec {
+                decimal: false,
+                ..spec(1f64, 1f64, 3f64, 0usize)
+            },
+        },
+        TestCase {
+            desc: "args 1_000, 0b10, 1_000.000_5".into(),
+            args: vec!["1_000".into(), "0b10".into(), "1_000.000_5".into()],
+            seq: SeqSpec {
+                decimal: false,
+                ..spec(1000f64, 2f64, 1000.0005f64, 4usize)
+            },
+        },
+        TestCase {
+            desc: "args 1, 0.5, 10.500".into(),
+            args: vec!["1".into(), ".5".into(), "10.5004".into()],
+            seq: spec(1f64, 0.5f64, 10.5004f64, 4usize),
+        },
+    ] {
+        assert_eq!(
+            getseq(&item.args).unwrap(),
+            item.seq,
+            "Should get expected sequence for {}",
+            item.desc,
+        );
+    }
+}
+
+#[test]
+fn test_bad_getseq() {
+    struct TestCase {
+        desc: String,
+        args: Vec<String>,
+        err: String,
+    }
+
+    for item in vec![
+        TestCase {
+            desc: "no args".into(),
+            args: vec![],
+            err: "Usage: seq [-w] [-f format] [-s string] [-t string] [first [incr]] last".into(),
+        },
+        TestCase {
+            desc: "too many args".into(),
+            args: vec!["x".into(); 4],
+            err: "Usage: seq [-w] [-f format] [-s string] [-t string] [first [incr]] last".into(),
+        },
+        TestCase {
+            desc: "zero increment".into(),
+            args: vec!["1".into(), "0".into(), "5".into()],
+            err: "seq: zero increment".into(),
+        },
+        TestCase {
+            desc: "zero decrement".into(),
+            args: vec!["1".into(), "0".into(), "-5".into()],
+            err: "seq: zero decrement".into(),
+        },
+        TestCase {
+            desc: "positive increment".into(),
+            args: vec!["1".into(), "-1".into(), "5".into()],
+            err: "seq: needs positive increment".into(),
+        },
+        TestCase {
+            desc: "negative decrement".into(),
+            args: vec!["1".into(), "1".into(), "-5".into()],
+            err: "seq: needs negative decrement".into(),
+        },
+        TestCase {
+            desc: "invalid float".into(),
+            args: vec!["x".into()],
+            err: "seq: invalid floating point argument: x".into(),
+        },
+        TestCase {
+            desc: "invalid float 2".into(),
+            args: vec!["1".into(), "y".into()],
+            err: "seq: invalid floating point argument: y".into(),
+        },
+        TestCase {
+            desc: "invalid float 3".into(),
+            args: vec!["1".into(), "1".into(), "⚽️".into()],
+            err: "seq: invalid floating point argument: ⚽️".into(),
+        },
+        TestCase {
+            desc: "nan".into(),
+            args: vec!["nan".into()],
+            err: "seq: invalid not-a-number argument: nan".into(),
+        },
+        TestCase {
+            desc: "nan increment".into(),
+            args: vec!["1".into(), "-NaN".into(), "5".into()],
+            err: "seq: invalid not-a-number argument: -NaN".into(),
+        },
+    ] {
+        match getseq(&item.args) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                item.err,
+                "Should get error for {}",
+                item.desc
+            ),
+            Ok(_) => panic!("Should get error for {}", item.desc),
+        }
+    }
+}
+
+#[test]
+fn test_increment() {
+    for (first, incr, last, exp) in [
+        (1.0, None, 3.0, Ok(1.0)),
+        (3.0, None, 1.0, Ok(-1.0)),
+        (1.0, None, 1.0, Ok(1.0)),
+        (1.0, Some(0.5), 3.0, Ok(0.5)),
+        (3.0, Some(-0.5), 1.0, Ok(-0.5)),
+        (1.0, Some(-0.0), 3.0, Err("seq: zero increment")),
+        (3.0, Some(0.0), 1.0, Err("seq: zero decrement")),
+        (1.0, Some(-1.0), 3.0, Err("seq: needs positive increment")),
+        (3.0, Some(1.0), 1.0, Err("seq: needs negative decrement")),
+        (
+            f64::NAN,
+            None,
+            1.0,
+            Err("seq: invalid not-a-number argument: NaN"),
+        ),
+        (
+            1.0,
+            Some(f64::NAN),
+            3.0,
+            Err("seq: invalid not-a-number argument: NaN"),
+        ),
+    ] {
+        assert_eq!(
+            increment(first, incr, last).map_err(|e| e.to_string()),
+            exp.map_err(String::from),
+            "Should get increment of {} {:?} {}",
+            first,
+            incr,
+            last
+        );
+    }
+}
+
+#[test]
+fn test_emitseq_run() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        seq: SeqSpec,
+        sep: &'a str,
+        width: usize,
+        term: Option<String>,
+        between: bool,
+        args: Vec<String>,
+        exp: String,
+    }
+
+    for item in vec![
+            TestCase {
+                desc: "1-3",
+                seq: spec(1.0, 1.0, 3.0, 0),
+                sep: "\n",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec!["3".into()],
+                exp: "1\n2\n3\n".into(),
+            },
+            TestCase {
+                desc: "neg 1-3",
+                seq: spec(-1.0, -1.0, -3.0, 0),
+                sep: "\n",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec!["--".into(), "-1".into(), "-3".into()],
+                exp: "-1\n-2\n-3\n".into(),
+            },
+            TestCase {
+                desc: "neg 3-1",
+                seq: spec(-3.0, 1.0, -1.0, 0),
+                sep: "\n",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec!["--".into(), "-3".into(), "-1".into()],
+                exp: "-3\n-2\n-1\n".into(),
+            },
+            TestCase {
+                desc: "neg 1-3 no dashes",
+                seq: spec(-1.0, -1.0, -3.0, 0),
+                sep: "\n",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec!["-1".into(), "-3".into()],
+                exp: "-1\n-2\n-3\n".into(),
+            },
+            TestCase {
+                desc: "neg to pos no dashes",
+                seq: spec(-1.0, 1.0, 1.0, 0),
+                sep: "\n",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec!["-1".into(), "1".into()],
+                exp: "-1\n0\n1\n".into(),
+            },
+            TestCase {
+                desc: "1, 3, 5",
+                seq: spec(1.0, 2.0, 5.0, 0),
+                sep: "\n",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec!["1".into(), "2".into(), "5".into()],
+                exp: "1\n3\n5\n".into(),
+            },
+            TestCase {
+                desc: "neg 1, 3, 5",
+                seq: spec(-1.0, -2.0, -5.0, 0),
+                sep: "\n",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec!["--".into(), "-1".into(), "-2".into(), "-5".into()],
+                exp: "-1\n-3\n-5\n".into(),
+            },
+            TestCase {
+                desc: "1.0-3.0",
+                seq: spec(1.0, 1.0, 3.0, 1),
+                sep: "\n",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec!["3.0".into()],
+                exp: "1.0\n2.0\n3.0\n".into(),
+            },
+            TestCase {
+                desc: "1-3 x 0.5",
+                seq: spec(1.0, 0.5, 3.0, 1),
+                sep: "\n",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec!["1".into(), "0.5".into(), "3".into()],
+                exp: "1.0\n1.5\n2.0\n2.5\n3.0\n".into(),
+            },
+            TestCase {
+                desc: "1-2.1 x 0.3",
+                seq: spec(1.0, 0.3, 2.1, 1),
+                sep: ",",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec!["-s,".into(), "1".into(), "0.3".into(), "2.1".into()],
+                exp: "1.0,1.3,1.6,1.9,".into(),
+            },
+            TestCase {
+                desc: "neg 1-2.1 x 0.3",
+                seq: spec(-1.0, -0.3, -2.1, 1),
+                sep: ",",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec![
+                    "-s,".into(),
+                    "--".into(),
+                    "-1".into(),
+                    "-0.3".into(),
+                    "-2.1".into(),
+                ],
+                exp: "-1.0,-1.3,-1.6,-1.9,".into(),
+            },
+            TestCase {
+                desc: "neg 1-2.1 x 0.3 no dashes",
+                seq: spec(-1.0, -0.3, -2.1, 1),
+                sep: ",",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec![
+                    "-1".into(),
+                    "-.3".into(),
+                    "-s".into(),
+                    ",".into(),
+                    "-2.1".into(),
+                ],
+                exp: "-1.0,-1.3,-1.6,-1.9,".into(),
+            },
+            TestCase {
+                desc: "1-3 precision 3",
+                seq: spec(1.0, 1.0, 3.0, 3),
+                sep: ",",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec!["-s,".into(), "3.000".into()],
+                exp: "1.000,2.000,3.000,".into(),
+            },
+            TestCase {
+                desc: "8-10 width 6 precision 3",
+                seq: spec(8.0, 1.0, 10.0, 3),
+                sep: ",",
+                width: 6,
+                term: None,
+                between: false,
+                args: vec!["-s,".into(), "-w".into(), "8.000".into(), "10".into()],
+                exp: "08.000,09.000,10.000,".into(),
+            },
+            TestCase {
+                desc: "8-10 x 0.25 width 5",
+                seq: spec(8.0, 0.25, 10.0, 2),
+                sep: ",",
+                width: 5,
+                term: None,
+                between: false,
+                args: vec![
+                    "-s,".into(),
+                    "-w".into(),
+                    "8".into(),
+                    ".25".into(),
+                    "10".into(),
+                ],
+                exp: "08.00,08.25,08.50,08.75,09.00,09.25,09.50,09.75,10.00,".into(),
+            },
+            TestCase {
+                desc: "simple 1-100",
+                seq: spec(1.0, 1.0, 100.0, 0),
+                sep: "\n",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec!["100".into()],
+                exp: (1..=100)
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join("\n")
+                    + "\n",
+            },
+            TestCase {
+                desc: "1-100 with alt sep",
+                seq: spec(1.0, 1.0, 100.0, 0),
+                sep: ":",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec!["-s:".into(), "100".into()],
+                exp: (1..=100)
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(":")
+                    + ":",
+            },
+            TestCase {
+                desc: "1-100 with width",
+                seq: spec(1.0, 1.0, 100.0, 0),
+                sep: "\n",
+                width: 3,
+                term: None,
+                between: false,
+                args: vec!["-w".into(), "100".into()],
+                exp: (1..=100)
+                    .map(|x| format!("{:0>3}", x))
+                    .collect::<Vec<String>>()
+                    .join("\n")
+                    + "\n",
+            },
+            TestCase {
+                desc: "1-100 with term",
+                seq: spec(1.0, 1.0, 100.0, 0),
+                sep: "\n",
+                width: 1,
+                term: Some("foo".into()),
+                between: false,
+                args: vec!["-tfoo".into(), "100".into()],
+                exp: (1..=100)
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join("\n")
+                    + "\n"
+                    + "foo",
+            },
+            TestCase {
+                desc: "-5 0.25 2",
+                seq: spec(-5.0, 0.25, 2.0, 2),
+                sep: ",",
+                width: 1,
+                term: None,
+                between: false,
+                args: vec!["-s,".into(), "--".into(), "-5".into(), "0.25".into(), "2".into()],
+                exp: "-5.00,-4.75,-4.50,-4.25,-4.00,-3.75,-3.50,-3.25,-3.00,-2.75,-2.50,-2.25,-2.00,-1.75,-1.50,-1.25,-1.00,-0.75,-0.50,-0.25,0.00,0.25,0.50,0.75,1.00,1.25,1.50,1.75,2.00,".into(),
+            },
+            TestCase {
+                desc: "gnu 1-3 with alt sep",
+                seq: spec(1.0, 1.0, 3.0, 0),
+                sep: ",",
+                width: 1,
+                term: None,
+                between: true,
+                args: vec!["--compat=gnu".into(), "-s,".into(), "3".into()],
+                exp: "1,2,3\n".into(),
+            },
+            TestCase {
+                desc: "gnu 1-3 with term",
+                seq: spec(1.0, 1.0, 3.0, 0),
+                sep: ", ",
+                width: 1,
+                term: Some(".\n".into()),
+                between: true,
+                args: vec![
+                    "--compat=gnu".into(),
+                    "-s, ".into(),
+                    "-t.\\n".into(),
+                    "3".into(),
+                ],
+                exp: "1, 2, 3.\n".into(),
+            },
+        ] {
+            let mut buf: Vec<u8> = vec![];
+            let fmt = Format::fixed(item.width, item.seq.precision);
+            let seps = Separators {
+                sep: item.sep.into(),
+                term: item.term.clone(),
+                between: item.between,
+            };
+            emitseq(&mut buf, &item.seq, &fmt, &seps)?;
+            assert_eq!(
+                item.exp,
+                String::from_utf8(buf).unwrap(),
+                "Invalid output for {}",
+                item.desc,
+            );
+
+            let mut buf: Vec<u8> = vec![];
+            run(&mut buf, &item.args)?;
+            assert_eq!(
+                item.exp,
+                String::from_utf8(buf).unwrap(),
+                "Invalid run output for {}",
+                item.desc,
+            );
+        }
+
+    Ok(())
+}
+
+#[test]
+fn test_run_format() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "general",
+            args: &["-f", "%g", "9", ".25", "10"],
+            exp: "9\n9.25\n9.5\n9.75\n10\n",
+        },
+        TestCase {
+            desc: "long double modifier",
+            args: &["-f", "%Lg", "1", "2"],
+            exp: "1\n2\n",
+        },
+        TestCase {
+            desc: "fixed precision",
+            args: &["-f", "%.3f", "3"],
+            exp: "1.000\n2.000\n3.000\n",
+        },
+        TestCase {
+            desc: "exponential",
+            args: &["-f%e", "-s,", "2"],
+            exp: "1.000000e+00,2.000000e+00,",
+        },
+        TestCase {
+            desc: "hexadecimal",
+            args: &["--format", "%a", "4"],
+            exp: "0x1p+0\n0x1p+1\n0x1.8p+1\n0x1p+2\n",
+        },
+        TestCase {
+            desc: "sign and zero padding",
+            args: &["-f", "%+05.1f", "-s ", "1", "2"],
+            exp: "+01.0 +02.0 ",
+        },
+        TestCase {
+            desc: "affixes and percent",
+            args: &["-f", "item-%03g%%", "2"],
+            exp: "item-001%\nitem-002%\n",
+        },
+        TestCase {
+            desc: "left justified",
+            args: &["-f", "%-4g|", "-s", "", "2"],
+            exp: "1   |2   |",
+        },
+    ] {
+        assert_eq!(
+            runseq(item.args)?,
+            item.exp,
+            "Invalid run output for {}",
+            item.desc
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bad_run_format() {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        err: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "format with equal width",
+            args: &["-w", "-f", "%g", "3"],
+            err: "seq: format string may not be specified when printing equal width strings",
+        },
+        TestCase {
+            desc: "unknown directive",
+            args: &["-f", "%d", "3"],
+            err: "seq: format '%d' has unknown %d directive",
+        },
+        TestCase {
+            desc: "two directives",
+            args: &["-f", "%g %g", "3"],
+            err: "seq: format '%g %g' has too many % directives",
+        },
+        TestCase {
+            desc: "no directive",
+            args: &["-f", "n", "3"],
+            err: "seq: format 'n' has no % directive",
+        },
+        TestCase {
+            desc: "huge precision",
+            args: &["-f", "%.70000f", "1", "1"],
+            err: "seq: format '%.70000f' has a precision greater than 65535",
+        },
+        TestCase {
+            desc: "huge width",
+            args: &["-f", "%99999999999999999999f", "1", "1"],
+            err: "seq: format '%99999999999999999999f' has a width greater than 65535",
+        },
+    ] {
+        match runseq(item.args) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                item.err,
+                "Should get error for {}",
+                item.desc
+            ),
+            Ok(_) => panic!("Should get error for {}", item.desc),
+        }
+    }
+}
+
+#[test]
+fn test_getexact() {
+    for (args, exp) in [
+        (vec!["3"], Some(("1", "1", "3", 0))),
+        (vec!["3", "1"], Some(("3", "-1", "1", 0))),
+        (vec!["1", "1"], Some(("1", "1", "1", 0))),
+        (vec!["0", "0.25", "1"], Some(("0.00", "0.25", "1.00", 2))),
+        (vec!["-1.5", "1e-3"], Some(("-1.500", "1.000", "0.001", 3))),
+        (
+            vec!["18446744073709551620", "18446744073709551610"],
+            Some(("18446744073709551620", "-1", "18446744073709551610", 0)),
+        ),
+        (vec!["1", "inf"], None),
+        (vec!["nan"], None),
+        (vec![], None),
+    ] {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        let exact = getexact(&args).map(|s| {
+            (
+                s.first.to_string(),
+                s.incr.to_string(),
+                s.last.to_string(),
+                s.precision,
+            )
+        });
+        assert_eq!(
+            exact,
+            exp.map(|s| (s.0.to_string(), s.1.to_string(), s.2.to_string(), s.3)),
+            "Should get exact sequence for {:?}",
+            args,
+        );
+    }
+}
+
+#[test]
+fn test_float_endpoints() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: &'a str,
+    }
+
+    // Formats force the float path, where first + incr * i may round past
+    // last.
+    for item in [
+        TestCase {
+            desc: "tenths to 0.3",
+            args: &["0", "0.1", "0.3"],
+            exp: "0,0.1,0.2,0.3,",
+        },
+        TestCase {
+            desc: "tenths to 0.7",
+            args: &["0.1", "0.1", "0.7"],
+            exp: "0.1,0.2,0.3,0.4,0.5,0.6,0.7,",
+        },
+        TestCase {
+            desc: "negative tenths",
+            args: &["1", "-0.1", "0.7"],
+            exp: "1,0.9,0.8,0.7,",
+        },
+        TestCase {
+            desc: "odd tenths",
+            args: &["0.1", "0.2", "0.9"],
+            exp: "0.1,0.3,0.5,0.7,0.9,",
+        },
+        TestCase {
+            desc: "multiples of 1.1",
+            args: &["1.1", "1.1", "3.3"],
+            exp: "1.1,2.2,3.3,",
+        },
+        TestCase {
+            desc: "last between items",
+            args: &["0", "0.1", "0.26"],
+            exp: "0,0.1,0.2,",
+        },
+        TestCase {
+            desc: "hundredths",
+            args: &["0", "0.01", "0.03"],
+            exp: "0,0.01,0.02,0.03,",
+        },
+        TestCase {
+            desc: "negative first",
+            args: &["-0.3", "0.1", "0"],
+            exp: "-0.3,-0.2,-0.1,0,",
+        },
+    ] {
+        assert_eq!(
+            runseq(&[&["-s,", "-f%g"], item.args].concat())?,
+            item.exp,
+            "Should include last for {}",
+            item.desc
+        );
+
+        let args: Vec<String> = item.args.iter().map(|x| x.to_string()).collect();
+        assert_eq!(
+            floatcount(&getseq(&args)?)?,
+            item.exp.matches(',').count() as u64,
+            "Should count {}",
+            item.desc
+        );
+    }
+
+    // Items of sequences with hexadecimal operands are not rounded, however
+    // large.
+    assert_eq!(
+        runseq(&["-s,", "-f%.4f", "1000000000", "0x1p-3", "1000000001"])?,
+        (0..9)
+            .map(|i| format!("{:.4},", 1e9 + i as f64 / 8.0))
+            .collect::<String>(),
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_run_exact() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: String,
+    }
+
+    for item in [
+        TestCase {
+            desc: "beyond u64",
+            args: &["18446744073709551610", "18446744073709551620"],
+            exp: (10u128..=20)
+                .map(|x| format!("{}\n", 18446744073709551600 + x))
+                .collect::<String>(),
+        },
+        TestCase {
+            desc: "nanoseconds",
+            args: &["-s,", "0", "0.000000001", "0.000000004"],
+            exp: "0.000000000,0.000000001,0.000000002,0.000000003,0.000000004,".into(),
+        },
+        TestCase {
+            desc: "nanoseconds to one",
+            args: &["-s,", "0.999999998", "0.000000001", "1"],
+            exp: "0.999999998,0.999999999,1.000000000,".into(),
+        },
+        TestCase {
+            desc: "tenths",
+            args: &["-s,", "0", "0.1", "0.3"],
+            exp: "0.0,0.1,0.2,0.3,".into(),
+        },
+        TestCase {
+            desc: "equal width halves",
+            args: &["-s,", "-w", "-1", ".5", "1"],
+            exp: "-1.0,-0.5,00.0,00.5,01.0,".into(),
+        },
+        TestCase {
+            desc: "more digits than f64",
+            args: &["-s,", "99999999999999999999", "100000000000000000001"],
+            exp: "99999999999999999999,100000000000000000000,100000000000000000001,".into(),
+        },
+    ] {
+        assert_eq!(
+            runseq(item.args)?,
+            item.exp,
+            "Invalid run output for {}",
+            item.desc
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_emitints() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        first: &'a str,
+        incr: &'a str,
+        last: &'a str,
+        width: usize,
+        exp: String,
+    }
+
+    for item in [
+        TestCase {
+            desc: "one",
+            first: "1",
+            incr: "1",
+            last: "1",
+            width: 1,
+            exp: "1,".into(),
+        },
+        TestCase {
+            desc: "zero to ten",
+            first: "0",
+            incr: "1",
+            last: "10",
+            width: 1,
+            exp: "0,1,2,3,4,5,6,7,8,9,10,".into(),
+        },
+        TestCase {
+            desc: "empty",
+            first: "5",
+            incr: "1",
+            last: "4",
+            width: 1,
+            exp: "".into(),
+        },
+        TestCase {
+            desc: "carry",
+            first: "998",
+            incr: "1",
+            last: "1001",
+            width: 1,
+            exp: "998,999,1000,1001,".into(),
+        },
+        TestCase {
+            desc: "wide increment",
+            first: "5",
+            incr: "995",
+            last: "3000",
+            width: 1,
+            exp: "5,1000,1995,2990,".into(),
+        },
+        TestCase {
+            desc: "increment exceeds last",
+            first: "7",
+            incr: "100",
+            last: "9",
+            width: 1,
+            exp: "7,".into(),
+        },
+        TestCase {
+            desc: "width",
+            first: "8",
+            incr: "1",
+            last: "10",
+            width: 3,
+            exp: "008,009,010,".into(),
+        },
+        TestCase {
+            desc: "beyond u64",
+            first: "18446744073709551614",
+            incr: "1",
+            last: "18446744073709551617",
+            width: 1,
+            exp: "18446744073709551614,18446744073709551615,18446744073709551616,18446744073709551617,"
+                .into(),
+        },
+        TestCase {
+            desc: "many",
+            first: "1",
+            incr: "3",
+            last: "100000",
+            width: 1,
+            exp: (1..=100000)
+                .step_by(3)
+                .map(|x| x.to_string() + ",")
+                .collect(),
+        },
+    ] {
+        let mut buf: Vec<u8> = vec![];
+        emitints(&mut buf, item.first, item.incr, item.last, item.width, &seps(",", None))?;
+        assert_eq!(
+            item.exp,
+            String::from_utf8(buf).unwrap(),
+            "Invalid output for {}",
+            item.desc,
+        );
+    }
+
+    let mut buf: Vec<u8> = vec![];
+    emitints(&mut buf, "1", "1", "3", 1, &seps("\n", Some("end")))?;
+    assert_eq!("1\n2\n3\nend", String::from_utf8(buf).unwrap());
+
+    let mut buf: Vec<u8> = vec![];
+    let gnu = Separators {
+        between: true,
+        ..seps(", ", None)
+    };
+    emitints(&mut buf, "1", "1", "3", 1, &gnu)?;
+    assert_eq!("1, 2, 3\n", String::from_utf8(buf).unwrap());
+
+    let mut buf: Vec<u8> = vec![];
+    emitints(&mut buf, "3", "1", "1", 1, &gnu)?;
+    assert_eq!("", String::from_utf8(buf).unwrap());
+
+    Ok(())
+}
+
+#[test]
+fn test_run_compat() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "default",
+            args: &["-s,", "9", ".25", "10"],
+            exp: "9.00,9.25,9.50,9.75,10.00,",
+        },
+        TestCase {
+            desc: "crab",
+            args: &["-s,", "--compat=crab", "9", ".25", "10"],
+            exp: "9.00,9.25,9.50,9.75,10.00,",
+        },
+        TestCase {
+            desc: "gnu",
+            args: &["-s,", "--compat=gnu", "9", ".25", "10"],
+            exp: "9.00,9.25,9.50,9.75,10.00\n",
+        },
+        TestCase {
+            desc: "gnu prints prefixed integers in decimal",
+            args: &["-s,", "--compat=gnu", "0x10", "0x12"],
+            exp: "16,17,18\n",
+        },
+        TestCase {
+            desc: "bsd",
+            args: &["-s,", "--compat=bsd", "9", ".25", "10"],
+            exp: "9,9.25,9.5,9.75,10,",
+        },
+        TestCase {
+            desc: "bsd with equal width",
+            args: &["-s,", "--compat", "bsd", "-w", "9", ".25", "10"],
+            exp: "09.00,09.25,09.50,09.75,10.00,",
+        },
+        TestCase {
+            desc: "bsd rounds to %g",
+            args: &["-s,", "--compat=bsd", "1000000", "1000001"],
+            exp: "1e+06,1e+06,",
+        },
+        TestCase {
+            desc: "bsd with format",
+            args: &["-s,", "--compat=bsd", "-f", "%.1f", "2"],
+            exp: "1.0,2.0,",
+        },
+        TestCase {
+            desc: "infinite increment",
+            args: &["-s,", "1.50", "inf", "3"],
+            exp: "1.50,",
+        },
+        TestCase {
+            desc: "gnu infinite increment",
+            args: &["-s,", "--compat=gnu", "1.50", "inf", "3"],
+            exp: "1.5\n",
+        },
+        TestCase {
+            desc: "default decrement",
+            args: &["-s,", "1", "0"],
+            exp: "1,0,",
+        },
+        TestCase {
+            desc: "gnu without decrement",
+            args: &["-s,", "--compat=gnu", "1", "0"],
+            exp: "",
+        },
+        TestCase {
+            desc: "gnu without items",
+            args: &["-s,", "--compat=gnu", "0"],
+            exp: "",
+        },
+        TestCase {
+            desc: "gnu radix without decrement",
+            args: &["-s,", "--compat=gnu", "-x", "0x10", "0x8"],
+            exp: "",
+        },
+        TestCase {
+            desc: "gnu increment",
+            args: &["-s,", "--compat=gnu", "0", "1"],
+            exp: "0,1\n",
+        },
+        TestCase {
+            desc: "gnu explicit decrement",
+            args: &["-s,", "--compat=gnu", "1", "-1", "0"],
+            exp: "1,0\n",
+        },
+        TestCase {
+            desc: "gnu product without decrement",
+            args: &["-s,", "--compat=gnu", "--product", "2:1", "1:2"],
+            exp: "",
+        },
+    ] {
+        assert_eq!(
+            runseq(item.args)?,
+            item.exp,
+            "Invalid run output for {}",
+            item.desc
+        );
+    }
+
+    match runseq(&["--compat=posix", "1"]) {
+        Err(e) => assert_eq!(e.to_string(), "seq: invalid compatibility mode: posix"),
+        Ok(_) => panic!("Should get error for invalid compat"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_run_radix() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "hexadecimal",
+            args: &["-s,", "-x", "8", "12"],
+            exp: "8,9,a,b,c,",
+        },
+        TestCase {
+            desc: "prefix",
+            args: &["-s,", "-x", "--prefix", "254", "257"],
+            exp: "0xfe,0xff,0x100,0x101,",
+        },
+        TestCase {
+            desc: "equal width",
+            args: &["-s,", "-xw", "254", "257"],
+            exp: "0fe,0ff,100,101,",
+        },
+        TestCase {
+            desc: "equal width prefix",
+            args: &["-s,", "-xw", "--prefix", "254", "257"],
+            exp: "0x0fe,0x0ff,0x100,0x101,",
+        },
+        TestCase {
+            desc: "octal",
+            args: &["-s,", "--radix=8", "-w", "6", "9"],
+            exp: "06,07,10,11,",
+        },
+        TestCase {
+            desc: "octal prefix",
+            args: &["-s,", "-o", "--prefix", "8"],
+            exp: "0o1,0o2,0o3,0o4,0o5,0o6,0o7,0o10,",
+        },
+        TestCase {
+            desc: "binary",
+            args: &["-s,", "-b", "-w", "4"],
+            exp: "001,010,011,100,",
+        },
+        TestCase {
+            desc: "binary prefix increment",
+            args: &["-s,", "-b", "--prefix", "-w", "0", "4", "16"],
+            exp: "0b00000,0b00100,0b01000,0b01100,0b10000,",
+        },
+        TestCase {
+            desc: "negative",
+            args: &["-s,", "-x", "-w", "-16", "8", "16"],
+            exp: "-10,-08,000,008,010,",
+        },
+        TestCase {
+            desc: "decimal",
+            args: &["-s,", "--radix=10", "3"],
+            exp: "1,2,3,",
+        },
+        TestCase {
+            desc: "beyond f64",
+            args: &["-s,", "-x", "9007199254740993", "9007199254740995"],
+            exp: "20000000000001,20000000000002,20000000000003,",
+        },
+        TestCase {
+            desc: "u64 max",
+            args: &["-s,", "-x", "18446744073709551613", "18446744073709551615"],
+            exp: "fffffffffffffffd,fffffffffffffffe,ffffffffffffffff,",
+        },
+        TestCase {
+            desc: "i128 min",
+            args: &[
+                "-s,",
+                "-x",
+                "-170141183460469231731687303715884105728",
+                "-170141183460469231731687303715884105727",
+            ],
+            exp: "-80000000000000000000000000000000,-7fffffffffffffffffffffffffffffff,",
+        },
+        TestCase {
+            desc: "count by prefixed",
+            args: &["-s,", "-x", "-n", "3", "--by", "0x10", "0xf0"],
+            exp: "f0,100,110,",
+        },
+        TestCase {
+            desc: "reverse",
+            args: &["-s,", "-x", "--reverse", "0x10", "0x12"],
+            exp: "12,11,10,",
+        },
+    ] {
+        assert_eq!(
+            runseq(item.args)?,
+            item.exp,
+            "Invalid run output for {}",
+            item.desc
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bad_run_radix() {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        err: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "fraction",
+            args: &["-x", "1.5"],
+            err: "seq: radix output requires integer arguments",
+        },
+        TestCase {
+            desc: "fractional increment",
+            args: &["-x", "1", ".5", "2"],
+            err: "seq: radix output requires integer arguments",
+        },
+        TestCase {
+            desc: "format",
+            args: &["-x", "-f%g", "2"],
+            err: "seq: format string may not be specified with a radix",
+        },
+        TestCase {
+            desc: "prefix without radix",
+            args: &["--prefix", "1", "3"],
+            err: "seq: --prefix requires a radix other than 10",
+        },
+        TestCase {
+            desc: "prefix with radix 10",
+            args: &["--prefix", "--radix=10", "1", "3"],
+            err: "seq: --prefix requires a radix other than 10",
+        },
+        TestCase {
+            desc: "prefix in GNU compat mode",
+            args: &["--prefix", "--compat=gnu", "0x1", "0x3"],
+            err: "seq: --prefix requires a radix other than 10",
+        },
+        TestCase {
+            desc: "prefix with format",
+            args: &["--prefix", "-f", "%g", "1", "3"],
+            err: "seq: --format may not be specified with --prefix",
+        },
+    ] {
+        match runseq(item.args) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                item.err,
+                "Should get error for {}",
+                item.desc
+            ),
+            Ok(_) => panic!("Should get error for {}", item.desc),
+        }
+    }
+}
+
+// Pipe accepts writes up to a limit, after which it fails as a pipe with a
+// closed read end.
+struct Pipe {
+    buf: Vec<u8>,
+    limit: usize,
+}
+
+impl Write for Pipe {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = cmp::min(buf.len(), self.limit - self.buf.len());
+        if n == 0 {
+            return Err(std::io::ErrorKind::BrokenPipe.into());
+        }
+        self.buf.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_run_infinite() {
+    for (args, exp) in [
+        (vec!["1", "inf"], "1\n2\n3\n4\n5\n"),
+        (vec!["-s,", "-1", "-inf"], "-1,-2,-3,-"),
+        (vec!["-f", "%.1f", "0", "0.5", "inf"], "0.0\n0.5\n1."),
+        (vec!["inf", "1"], "inf\ninf\nin"),
+        (vec!["--output", "ndjson", "1", "inf"], "{\"index\":0"),
+        (vec!["--output=json", "-1", "-inf"], "[-1,-2,-3,"),
+    ] {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        let mut pipe = Pipe {
+            buf: vec![],
+            limit: 10,
+        };
+        match run(&mut pipe, &args) {
+            Err(e) => assert!(
+                matches!(&e, SeqError::Io(err) if err.kind() == std::io::ErrorKind::BrokenPipe),
+                "Should get broken pipe, got {}",
+                e
+            ),
+            Ok(_) => panic!("Should not end {:?}", args),
+        }
+        assert_eq!(
+            String::from_utf8(pipe.buf).unwrap(),
+            exp,
+            "Should stream {:?}",
+            args
+        );
+    }
+}
+
+#[test]
+fn test_run_operands() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "hexadecimal",
+            args: &["-s,", "0x10", "0x13"],
+            exp: "0x10,0x11,0x12,0x13,",
+        },
+        TestCase {
+            desc: "hexadecimal equal width",
+            args: &["-s,", "-w", "0x8", "0x10"],
+            exp: "0x08,0x09,0x0a,0x0b,0x0c,0x0d,0x0e,0x0f,0x10,",
+        },
+        TestCase {
+            desc: "octal",
+            args: &["-s,", "0o6", "0o10"],
+            exp: "0o6,0o7,0o10,",
+        },
+        TestCase {
+            desc: "binary",
+            args: &["-s,", "0b1", "0b11"],
+            exp: "0b1,0b10,0b11,",
+        },
+        TestCase {
+            desc: "negative hexadecimal",
+            args: &["-s,", "-0x2", "0x1"],
+            exp: "-0x2,-0x1,0x0,0x1,",
+        },
+        TestCase {
+            desc: "mixed radix and decimal",
+            args: &["-s,", "1", "0x3"],
+            exp: "0x1,0x2,0x3,",
+        },
+        TestCase {
+            desc: "radix 10",
+            args: &["-s,", "--radix=10", "0x10", "0x12"],
+            exp: "16,17,18,",
+        },
+        TestCase {
+            desc: "binary output",
+            args: &["-s,", "-b", "0x4", "0x6"],
+            exp: "100,101,110,",
+        },
+        TestCase {
+            desc: "format",
+            args: &["-s,", "-f", "%g", "0x10", "0x12"],
+            exp: "16,17,18,",
+        },
+        TestCase {
+            desc: "hexadecimal floats",
+            args: &["-s,", "0x1p1", "0x1.8p2"],
+            exp: "2,3,4,5,6,",
+        },
+        TestCase {
+            desc: "gnu hexadecimal floats",
+            args: &["-s,", "--compat=gnu", "0x.8p1", "0x.8", "2"],
+            exp: "1,1.5,2\n",
+        },
+        TestCase {
+            desc: "binary fraction",
+            args: &["-s,", "0x1p-3", "0x1p-3", "0x1p-1"],
+            exp: "0.125,0.250,0.375,0.500,",
+        },
+        TestCase {
+            desc: "hexadecimal fraction",
+            args: &["-s,", "0x1.8p1", "0x.1", "0x1.9p1"],
+            exp: "3.0000,3.0625,3.1250,",
+        },
+        TestCase {
+            desc: "beyond f64",
+            args: &["-s,", "0xffffffffffffff00", "0xffffffffffffff03"],
+            exp: "0xffffffffffffff00,0xffffffffffffff01,0xffffffffffffff02,0xffffffffffffff03,",
+        },
+        TestCase {
+            desc: "beyond f64 in radix 10",
+            args: &[
+                "-s,",
+                "--radix=10",
+                "0xffffffffffffff00",
+                "0xffffffffffffff02",
+            ],
+            exp: "18446744073709551360,18446744073709551361,18446744073709551362,",
+        },
+        TestCase {
+            desc: "grouped",
+            args: &["-s,", "1_000", "1_002"],
+            exp: "1000,1001,1002,",
+        },
+        TestCase {
+            desc: "grouped fraction",
+            args: &["-s,", "0.000_5", "0.000_1", "0.000_7"],
+            exp: "0.0005,0.0006,0.0007,",
+        },
+        TestCase {
+            desc: "grouped negative",
+            args: &["-s,", "-1_000", "-998"],
+            exp: "-1000,-999,-998,",
+        },
+        TestCase {
+            desc: "exponents",
+            args: &["-s,", "1e-3", "1e-3", "3e-3"],
+            exp: "0.001,0.002,0.003,",
+        },
+        TestCase {
+            desc: "exponents with format",
+            args: &["-s,", "-f", "%g", "1e-3", "1e-3", "3e-3"],
+            exp: "0.001,0.002,0.003,",
+        },
+        TestCase {
+            desc: "scientific",
+            args: &["-s,", "--scientific", "1e-3", "1e-3", "3e-3"],
+            exp: "1e-03,2e-03,3e-03,",
+        },
+        TestCase {
+            desc: "scientific precision",
+            args: &["-s,", "--scientific", "1.5e2", "1", "152"],
+            exp: "1.50e+02,1.51e+02,1.52e+02,",
+        },
+        TestCase {
+            desc: "scientific equal width",
+            args: &["-s,", "--scientific", "-w", "-1", "1"],
+            exp: "-1e+00,00e+00,01e+00,",
+        },
+        TestCase {
+            desc: "scientific reverse",
+            args: &["-s,", "--scientific", "--reverse", "1", "0.5", "2"],
+            exp: "2.0e+00,1.5e+00,1.0e+00,",
+        },
+    ] {
+        assert_eq!(
+            runseq(item.args)?,
+            item.exp,
+            "Invalid run output for {}",
+            item.desc
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_run_order() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "last",
+            args: &["-s,", "--reverse", "3"],
+            exp: "3,2,1,",
+        },
+        TestCase {
+            desc: "increment",
+            args: &["-s,", "--reverse", "1", "3", "10"],
+            exp: "10,7,4,1,",
+        },
+        TestCase {
+            desc: "uneven increment",
+            args: &["-s,", "--reverse", "1", "0.3", "2.1"],
+            exp: "1.9,1.6,1.3,1.0,",
+        },
+        TestCase {
+            desc: "decrement",
+            args: &["-s,", "--reverse", "10", "-3", "0"],
+            exp: "1,4,7,10,",
+        },
+        TestCase {
+            desc: "equal width",
+            args: &["-s,", "--reverse", "-w", "8", "10"],
+            exp: "10,09,08,",
+        },
+        TestCase {
+            desc: "format",
+            args: &["-s,", "--reverse", "-f", "%g", "0", "0.25", "1"],
+            exp: "1,0.75,0.5,0.25,0,",
+        },
+        TestCase {
+            desc: "radix",
+            args: &["-s,", "--reverse", "-x", "--prefix", "14", "17"],
+            exp: "0x11,0x10,0xf,0xe,",
+        },
+        TestCase {
+            desc: "terminator",
+            args: &["-s,", "--reverse", "-t", "end", "2"],
+            exp: "2,1,end",
+        },
+        TestCase {
+            desc: "default decrement",
+            args: &["-s,", "--reverse", "2", "1"],
+            exp: "1,2,",
+        },
+        TestCase {
+            desc: "beyond u64",
+            args: &[
+                "-s,",
+                "--reverse",
+                "18446744073709551618",
+                "18446744073709551620",
+            ],
+            exp: "18446744073709551620,18446744073709551619,18446744073709551618,",
+        },
+    ] {
+        assert_eq!(
+            runseq(item.args)?,
+            item.exp,
+            "Invalid run output for {}",
+            item.desc
+        );
+    }
+
+    // Shuffles should emit the forward sequence in a stable random order.
+    for args in [
+        vec!["100"],
+        vec!["0", "0.5", "20"],
+        vec!["-f", "%.1f", "1", "0.3", "30"],
+        vec!["-x", "0", "7", "1000"],
+    ] {
+        let output = |extra: &[&str]| -> Result<Vec<String>> {
+            Ok(runseq(&[extra, &args].concat())?
+                .lines()
+                .map(String::from)
+                .collect())
+        };
+
+        let forward = output(&[])?;
+        let shuffled = output(&["--shuffle=42"])?;
+        assert_ne!(forward, shuffled, "Should shuffle {:?}", args);
+        assert_eq!(shuffled, output(&["--shuffle=42"])?, "Should be stable");
+        assert_ne!(shuffled, output(&["--shuffle=7"])?, "Should vary by seed");
+        let (mut a, mut b) = (forward.clone(), shuffled.clone());
+        a.sort();
+        b.sort();
+        assert_eq!(a, b, "Should shuffle the same items for {:?}", args);
+    }
+
+    match runseq(&["--reverse", "inf"]) {
+        Err(e) => assert_eq!(e.to_string(), "seq: sequence too long"),
+        Ok(_) => panic!("Should get error reversing infinite sequence"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_run_count() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "count",
+            args: &["-s,", "--count", "3"],
+            exp: "1,2,3,",
+        },
+        TestCase {
+            desc: "first",
+            args: &["-s,", "-n", "3", "10"],
+            exp: "10,11,12,",
+        },
+        TestCase {
+            desc: "by fraction",
+            args: &["-s,", "-n", "4", "--by", "0.5", "1"],
+            exp: "1.0,1.5,2.0,2.5,",
+        },
+        TestCase {
+            desc: "by negative",
+            args: &["-s,", "-n", "3", "--by", "-2", "1"],
+            exp: "1,-1,-3,",
+        },
+        TestCase {
+            desc: "zero",
+            args: &["-s,", "-n", "0", "1"],
+            exp: "",
+        },
+        TestCase {
+            desc: "first and last",
+            args: &["-s,", "-n", "5", "0", "1"],
+            exp: "0.00,0.25,0.50,0.75,1.00,",
+        },
+        TestCase {
+            desc: "exclude last",
+            args: &["-s,", "-n", "4", "--exclude-last", "0", "1"],
+            exp: "0.00,0.25,0.50,0.75,",
+        },
+        TestCase {
+            desc: "decreasing",
+            args: &["-s,", "-n", "3", "1", "-1"],
+            exp: "1,0,-1,",
+        },
+        TestCase {
+            desc: "thirds",
+            args: &["-s,", "-n", "4", "0", "1"],
+            exp: "0.000000000000000,0.333333333333333,0.666666666666667,1.000000000000000,",
+        },
+        TestCase {
+            desc: "equal width",
+            args: &["-s,", "-n", "3", "-w", "0", "10"],
+            exp: "00,05,10,",
+        },
+        TestCase {
+            desc: "format",
+            args: &["-s,", "-n", "3", "-f", "%g", "0", "1"],
+            exp: "0,0.5,1,",
+        },
+        TestCase {
+            desc: "reverse",
+            args: &["-s,", "-n", "3", "--reverse", "0", "1"],
+            exp: "1.0,0.5,0.0,",
+        },
+        TestCase {
+            desc: "by tenths",
+            args: &["-s,", "-n", "3", "--by", "0.1", "0.1"],
+            exp: "0.1,0.2,0.3,",
+        },
+        TestCase {
+            desc: "beyond u64",
+            args: &["-s,", "-n", "2", "18446744073709551615"],
+            exp: "18446744073709551615,18446744073709551616,",
+        },
+    ] {
+        assert_eq!(
+            runseq(item.args)?,
+            item.exp,
+            "Invalid run output for {}",
+            item.desc
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bad_run_count() {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        err: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "not a number",
+            args: &["-n", "x", "1"],
+            err: "seq: invalid count: x",
+        },
+        TestCase {
+            desc: "negative",
+            args: &["-n", "-1", "1"],
+            err: "seq: invalid count: -1",
+        },
+    ] {
+        match runseq(item.args) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                item.err,
+                "Should get error for {}",
+                item.desc
+            ),
+            Ok(_) => panic!("Should get error for {}", item.desc),
+        }
+    }
+}
+
+#[test]
+fn test_getgeoseq() -> Result<()> {
+    for (args, n, by, logspace, exp) in [
+        (
+            vec!["1", "2", "8"],
+            None,
+            None,
+            false,
+            spec(1.0, 2.0, 8.0, 0),
+        ),
+        (
+            vec!["-1", "0.5", "-0.25"],
+            None,
+            None,
+            false,
+            spec(-1.0, 0.5, -0.25, 0),
+        ),
+        (vec![], Some(4), None, false, spec(1.0, 2.0, 8.0, 0)),
+        (
+            vec!["3"],
+            Some(3),
+            Some("10"),
+            false,
+            spec(3.0, 10.0, 300.0, 0),
+        ),
+        (
+            vec!["1", "1000"],
+            Some(4),
+            None,
+            true,
+            spec(1.0, 10.0, 1000.0, 0),
+        ),
+        (vec!["2", "2"], Some(1), None, true, spec(2.0, 1.0, 2.0, 0)),
+    ] {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        let seq = getgeoseq(&args, n, by, false, logspace)?;
+        assert!(
+            (seq.incr - exp.incr).abs() < 1e-12 && (seq.last - exp.last).abs() < 1e-9,
+            "Should get sequence {:?} for {:?}, got {:?}",
+            exp,
+            args,
+            seq
+        );
+        assert_eq!(seq.first, exp.first, "Should get first for {:?}", args);
+    }
+
+    for (args, n, by, logspace, err) in [
+        (
+            vec!["1", "8"],
+            None,
+            None,
+            false,
+            "seq: geometric sequence requires first, ratio, and last",
+        ),
+        (
+            vec!["1", "1", "8"],
+            None,
+            None,
+            false,
+            "seq: ratio must not be 1",
+        ),
+        (
+            vec!["1", "-2", "8"],
+            None,
+            None,
+            false,
+            "seq: ratio must be positive",
+        ),
+        (
+            vec!["8", "2", "1"],
+            None,
+            None,
+            false,
+            "seq: needs ratio less than 1",
+        ),
+        (
+            vec!["1", "0.5", "8"],
+            None,
+            None,
+            false,
+            "seq: needs ratio greater than 1",
+        ),
+        (
+            vec!["0", "2", "8"],
+            None,
+            None,
+            false,
+            "seq: geometric sequence must not start at zero",
+        ),
+        (
+            vec!["-1", "8"],
+            Some(3),
+            None,
+            false,
+            "seq: first and last must be non-zero with the same sign",
+        ),
+        (
+            vec!["1", "8"],
+            Some(3),
+            Some("2"),
+            false,
+            "seq: --by may not be specified with both first and last",
+        ),
+        (
+            vec!["1", "8"],
+            None,
+            None,
+            true,
+            "seq: --logspace requires -n, first, and last",
+        ),
+        (
+            vec!["1"],
+            Some(3),
+            None,
+            true,
+            "seq: --logspace requires -n, first, and last",
+        ),
+    ] {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        match getgeoseq(&args, n, by, false, logspace) {
+            Err(e) => assert_eq!(e.to_string(), err, "Should get error for {:?}", args),
+            Ok(_) => panic!("Should get error for {:?}", args),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_run_geometric() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "multiply",
+            args: &["-s,", "--multiply", "1", "2", "16"],
+            exp: "1,2,4,8,16,",
+        },
+        TestCase {
+            desc: "ratio 3",
+            args: &["-s,", "--geometric", "1", "3", "100"],
+            exp: "1,3,9,27,81,",
+        },
+        TestCase {
+            desc: "ratio 0.1",
+            args: &["-s,", "--geometric", "1000", "0.1", "0.001"],
+            exp: "1000,100,10,1,0.1,0.01,0.001,",
+        },
+        TestCase {
+            desc: "negative",
+            args: &["-s,", "--geometric", "-1", "2", "-8"],
+            exp: "-1,-2,-4,-8,",
+        },
+        TestCase {
+            desc: "equal width",
+            args: &["-s,", "--geometric", "-w", "1", "10", "1000"],
+            exp: "0001,0010,0100,1000,",
+        },
+        TestCase {
+            desc: "format",
+            args: &["-s,", "--geometric", "-f", "%.1f", "1", "2", "4"],
+            exp: "1.0,2.0,4.0,",
+        },
+        TestCase {
+            desc: "reverse",
+            args: &["-s,", "--geometric", "--reverse", "1", "2", "8"],
+            exp: "8,4,2,1,",
+        },
+        TestCase {
+            desc: "count",
+            args: &["-s,", "--geometric", "-n", "4"],
+            exp: "1,2,4,8,",
+        },
+        TestCase {
+            desc: "count by half",
+            args: &["-s,", "--geometric", "-n", "3", "--by", "0.5", "4"],
+            exp: "4,2,1,",
+        },
+        TestCase {
+            desc: "logspace",
+            args: &["-s,", "--logspace", "-n", "5", "1", "10000"],
+            exp: "1,10,100,1000,10000,",
+        },
+        TestCase {
+            desc: "logspace exclude last",
+            args: &[
+                "-s,",
+                "--logspace",
+                "-n",
+                "4",
+                "--exclude-last",
+                "1",
+                "10000",
+            ],
+            exp: "1,10,100,1000,",
+        },
+        TestCase {
+            desc: "logspace fractions",
+            args: &["-s,", "--logspace", "-n", "3", "0.01", "1"],
+            exp: "0.01,0.1,1,",
+        },
+        TestCase {
+            desc: "logspace in radix",
+            args: &["-s,", "--logspace", "-n", "3", "-x", "1", "256"],
+            exp: "1,10,100,",
+        },
+    ] {
+        assert_eq!(
+            runseq(item.args)?,
+            item.exp,
+            "Invalid run output for {}",
+            item.desc
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_getdateseq() -> Result<()> {
+    let date = |s| DateTime::parse(s).unwrap().0;
+    for (args, n, by, exp) in [
+        (
+            vec!["2026-01-01", "2026-01-03"],
+            None,
+            None,
+            (date("2026-01-01"), "1d", date("2026-01-03"), false),
+        ),
+        (
+            vec!["2026-01-03", "2026-01-01"],
+            None,
+            None,
+            (date("2026-01-03"), "-1d", date("2026-01-01"), false),
+        ),
+        (
+            vec!["2026-01-01", "1mo", "2026-12-01T12:00"],
+            None,
+            None,
+            (date("2026-01-01"), "1mo", date("2026-12-01T12:00"), true),
+        ),
+        (
+            vec!["2026-01-01"],
+            Some(3),
+            None,
+            (date("2026-01-01"), "1d", date("2026-01-03"), false),
+        ),
+        (
+            vec!["2026-01-01"],
+            Some(3),
+            Some("PT12H"),
+            (date("2026-01-01"), "PT12H", date("2026-01-02"), true),
+        ),
+    ] {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        let exp = (exp.0, Duration::parse(exp.1).unwrap(), exp.2, exp.3);
+        assert_eq!(
+            getdateseq(&args, n, by)?,
+            exp,
+            "Should get dates for {:?}",
+            args
+        );
+    }
+
+    for (args, n, by, err) in [
+        (vec!["2026-01-01"], None, None, usage!()),
+        (
+            vec!["2026-01-01", "1d", "2026-02-01", "1"],
+            None,
+            None,
+            usage!(),
+        ),
+        (
+            vec!["2026-01-01", "2026-01-02"],
+            Some(2),
+            Some("1d"),
+            "seq: --by may not be specified with both first and last",
+        ),
+        (
+            vec!["2026-1-1", "2026-01-02"],
+            None,
+            None,
+            "seq: invalid date argument: 2026-1-1",
+        ),
+        (
+            vec!["2026-01-01", "1m", "2026-01-02"],
+            None,
+            None,
+            "seq: invalid duration argument: 1m",
+        ),
+        (
+            vec!["2026-01-01"],
+            Some(2),
+            Some("x"),
+            "seq: invalid duration argument: x",
+        ),
+        (
+            vec!["2026-01-01", "0d", "2026-01-02"],
+            None,
+            None,
+            "seq: zero increment",
+        ),
+        (
+            vec!["2026-01-01", "-1d", "2026-01-02"],
+            None,
+            None,
+            "seq: needs positive increment",
+        ),
+        (
+            vec!["2026-01-02", "1d", "2026-01-01"],
+            None,
+            None,
+            "seq: needs negative decrement",
+        ),
+        (vec!["9999-12-31"], Some(2), None, "seq: date out of range"),
+    ] {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        match getdateseq(&args, n, by) {
+            Err(e) => assert_eq!(e.to_string(), err, "Should get error for {:?}", args),
+            Ok(_) => panic!("Should get error for {:?}", args),
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_run_date() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "days",
+            args: &["-s,", "--date", "2026-01-01", "2026-01-03"],
+            exp: "2026-01-01,2026-01-02,2026-01-03,",
+        },
+        TestCase {
+            desc: "one day",
+            args: &["-s,", "--date", "2026-01-01", "1d", "2026-01-03"],
+            exp: "2026-01-01,2026-01-02,2026-01-03,",
+        },
+        TestCase {
+            desc: "minus one day",
+            args: &["-s,", "--date", "2026-01-03", "-1d", "2026-01-01"],
+            exp: "2026-01-03,2026-01-02,2026-01-01,",
+        },
+        TestCase {
+            desc: "months clamp",
+            args: &["-s,", "--date", "2026-01-31", "P1M", "2026-05-01"],
+            exp: "2026-01-31,2026-02-28,2026-03-31,2026-04-30,",
+        },
+        TestCase {
+            desc: "years clamp",
+            args: &["-s,", "--date", "2024-02-29", "1y", "2028-03-01"],
+            exp: "2024-02-29,2025-02-28,2026-02-28,2027-02-28,2028-02-29,",
+        },
+        TestCase {
+            desc: "hours",
+            args: &[
+                "-s,",
+                "--date",
+                "2026-01-01T22:00",
+                "1h",
+                "2026-01-02T00:00",
+            ],
+            exp: "2026-01-01T22:00:00,2026-01-01T23:00:00,2026-01-02T00:00:00,",
+        },
+        TestCase {
+            desc: "format",
+            args: &[
+                "-s,",
+                "--date",
+                "-f",
+                "events_%Y%m%d",
+                "2026-02-27",
+                "2026-03-01",
+            ],
+            exp: "events_20260227,events_20260228,events_20260301,",
+        },
+        TestCase {
+            desc: "count by week",
+            args: &["-s,", "--date", "-n", "3", "--by", "1w", "2026-01-01"],
+            exp: "2026-01-01,2026-01-08,2026-01-15,",
+        },
+        TestCase {
+            desc: "count by minutes",
+            args: &["-s,", "--date", "-n", "2", "--by", "30min", "2026-01-01"],
+            exp: "2026-01-01T00:00:00,2026-01-01T00:30:00,",
+        },
+        TestCase {
+            desc: "reverse",
+            args: &["-s,", "--date", "--reverse", "2026-01-01", "2026-01-03"],
+            exp: "2026-01-03,2026-01-02,2026-01-01,",
+        },
+        TestCase {
+            desc: "terminator",
+            args: &["-s,", "--date", "-t", "end", "2026-01-01", "2026-01-02"],
+            exp: "2026-01-01,2026-01-02,end",
+        },
+    ] {
+        assert_eq!(
+            runseq(item.args)?,
+            item.exp,
+            "Invalid run output for {}",
+            item.desc
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bad_run_date() {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        err: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "radix",
+            args: &["--date", "-x", "2026-01-01", "2026-01-02"],
+            err: "seq: --date may not be specified with -x",
+        },
+        TestCase {
+            desc: "geometric",
+            args: &["--date", "--geometric", "2026-01-01", "2026-01-02"],
+            err: "seq: --date may not be specified with --geometric",
+        },
+        TestCase {
+            desc: "equal width",
+            args: &["--date", "-w", "2026-01-01", "2026-01-02"],
+            err: "seq: --date may not be specified with -w",
+        },
+        TestCase {
+            desc: "unknown directive",
+            args: &["--date", "-f", "%Q", "2026-01-01", "2026-01-02"],
+            err: "seq: date format '%Q' has unknown %Q directive",
+        },
+    ] {
+        match runseq(item.args) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                item.err,
+                "Should get error for {}",
+                item.desc
+            ),
+            Ok(_) => panic!("Should get error for {}", item.desc),
+        }
+    }
+}
+
+#[test]
+fn test_run_numeric() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "group",
+            args: &["-s,", "--group", "999", "1001"],
+            exp: "999,1,000,1,001,",
+        },
+        TestCase {
+            desc: "group with underscore",
+            args: &["-s,", "--group=_", "999999", "1000000"],
+            exp: "999_999,1_000_000,",
+        },
+        TestCase {
+            desc: "group with period",
+            args: &["-s,", "--group=.", "--decimal-point=,", "999.5", "1000.5"],
+            exp: "999,5,1.000,5,",
+        },
+        TestCase {
+            desc: "decimal comma",
+            args: &["-s,", "--decimal-point=,", "0", "0.5", "1"],
+            exp: "0,0,0,5,1,0,",
+        },
+        TestCase {
+            desc: "format grouping flag in the C locale",
+            args: &["-s;", "-f", "%'.0f", "1000", "1001"],
+            exp: "1000;1001;",
+        },
+        TestCase {
+            desc: "format grouping flag in a locale",
+            args: &["-s;", "--locale=de_DE", "-f", "%'.1f", "1000", "1001"],
+            exp: "1.000,0;1.001,0;",
+        },
+        TestCase {
+            desc: "format grouping flag with group",
+            args: &["-s;", "--group=_", "-f", "%'g", "1000", "1001"],
+            exp: "1_000;1_001;",
+        },
+        TestCase {
+            desc: "format without grouping flag in a locale",
+            args: &["-s;", "--locale=de_DE", "-f", "%.1f", "1000", "1001"],
+            exp: "1000,0;1001,0;",
+        },
+        TestCase {
+            desc: "equal width group",
+            args: &["-s,", "-w", "--group", "1", "1000"],
+            exp: "0,001",
+        },
+        TestCase {
+            desc: "negative equal width group",
+            args: &["-s,", "-w", "--group", "-1000", "1000", "1000"],
+            exp: "-1,000,00,000,01,000,",
+        },
+        TestCase {
+            desc: "group with format",
+            args: &["-s,", "--group", "-f", "%.2f", "1234", "1235"],
+            exp: "1,234.00,1,235.00,",
+        },
+        TestCase {
+            desc: "group exponents",
+            args: &["-s,", "--group", "1e3", "1e3", "2e3"],
+            exp: "1,000,2,000,",
+        },
+        TestCase {
+            desc: "german locale",
+            args: &["-s,", "--locale=de_DE.UTF-8", "--group", "999.5", "1000.5"],
+            exp: "999,5,1.000,5,",
+        },
+        TestCase {
+            desc: "german locale without group",
+            args: &["-s,", "--locale=de_DE", "1000.5", "1001.5"],
+            exp: "1000,5,1001,5,",
+        },
+        TestCase {
+            desc: "c locale",
+            args: &["-s,", "--locale=C", "--group", "999", "1000"],
+            exp: "999,1,000,",
+        },
+        TestCase {
+            desc: "group reverse",
+            args: &["-s,", "--group", "--reverse", "999", "1000"],
+            exp: "1,000,999,",
+        },
+        TestCase {
+            desc: "group ignores radix",
+            args: &["-s,", "--group", "0x3e7", "0x3e8"],
+            exp: "0x3e7,0x3e8,",
+        },
+    ] {
+        let out = runseq(item.args)?;
+        assert!(
+            out.starts_with(item.exp),
+            "Invalid run output for {}: {}",
+            item.desc,
+            out
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bad_run_numeric() {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        err: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "long decimal point",
+            args: &["--decimal-point=ab", "1"],
+            err: "seq: invalid decimal point: ab",
+        },
+        TestCase {
+            desc: "digit decimal point",
+            args: &["--decimal-point=5", "1"],
+            err: "seq: invalid decimal point: 5",
+        },
+        TestCase {
+            desc: "unknown locale",
+            args: &["--locale=xx_XX", "1"],
+            err: "seq: unknown locale: xx_XX",
+        },
+        TestCase {
+            desc: "date with group",
+            args: &["--date", "--group", "2026-01-01", "2026-01-02"],
+            err: "seq: --date may not be specified with --group",
+        },
+    ] {
+        match runseq(item.args) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                item.err,
+                "Should get error for {}",
+                item.desc
+            ),
+            Ok(_) => panic!("Should get error for {}", item.desc),
+        }
+    }
+}
+
+#[test]
+fn test_run_pad() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "negative zeros",
+            args: &["-s,", "-w", "-5", "5", "5"],
+            exp: "-5,00,05,",
+        },
+        TestCase {
+            desc: "negative two digits",
+            args: &["-s,", "-w", "-10", "5", "0"],
+            exp: "-10,-05,000,",
+        },
+        TestCase {
+            desc: "negative fraction",
+            args: &["-s,", "-w", "-1", "0.5", "0"],
+            exp: "-1.0,-0.5,00.0,",
+        },
+        TestCase {
+            desc: "space",
+            args: &["-s,", "--pad=space", "-w", "-5", "5", "5"],
+            exp: "-5, 0, 5,",
+        },
+        TestCase {
+            desc: "space two digits",
+            args: &["-s,", "--pad=space", "-w", "-10", "5", "0"],
+            exp: "-10, -5,  0,",
+        },
+        TestCase {
+            desc: "zero",
+            args: &["-s,", "--pad=zero", "-w", "8", "10"],
+            exp: "08,09,10,",
+        },
+        TestCase {
+            desc: "width",
+            args: &["-s,", "--width", "4", "-5", "5", "5"],
+            exp: "-005,0000,0005,",
+        },
+        TestCase {
+            desc: "width space",
+            args: &["-s,", "--width", "4", "--pad=space", "-5", "5", "5"],
+            exp: "  -5,   0,   5,",
+        },
+        TestCase {
+            desc: "width space growing",
+            args: &["-s,", "--width", "4", "--pad=space", "8", "10"],
+            exp: "   8,   9,  10,",
+        },
+        TestCase {
+            desc: "narrow width",
+            args: &["-s,", "--width", "1", "-w", "8", "10"],
+            exp: "8,9,10,",
+        },
+        TestCase {
+            desc: "width fraction",
+            args: &["-s,", "--width", "5", "-1", "0.5", "0"],
+            exp: "-01.0,-00.5,000.0,",
+        },
+        TestCase {
+            desc: "width radix",
+            args: &["-s,", "--width", "5", "-x", "255", "256"],
+            exp: "000ff,00100,",
+        },
+        TestCase {
+            desc: "width space radix",
+            args: &["-s,", "--width", "5", "--pad=space", "-x", "-1", "1"],
+            exp: "   -1,    0,    1,",
+        },
+        TestCase {
+            desc: "width reverse",
+            args: &["-s,", "--width", "3", "--reverse", "-1", "1"],
+            exp: "001,000,-01,",
+        },
+        TestCase {
+            desc: "width space group",
+            args: &[
+                "-s,",
+                "--width",
+                "6",
+                "--pad=space",
+                "--group",
+                "999",
+                "1000",
+            ],
+            exp: "   999, 1,000,",
+        },
+        TestCase {
+            desc: "bsd width",
+            args: &["-s,", "--compat=bsd", "--width", "3", "1", "2"],
+            exp: "001,002,",
+        },
+    ] {
+        assert_eq!(
+            runseq(item.args)?,
+            item.exp,
+            "Invalid run output for {}",
+            item.desc
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bad_run_pad() {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        err: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "width not a number",
+            args: &["--width", "x", "1"],
+            err: "seq: invalid width: x",
+        },
+        TestCase {
+            desc: "negative width",
+            args: &["--width", "-1", "1"],
+            err: "seq: invalid width: -1",
+        },
+        TestCase {
+            desc: "huge width",
+            args: &["--width", "100000000000", "1", "2"],
+            err: "seq: width greater than 65535: 100000000000",
+        },
+        TestCase {
+            desc: "largest usize width",
+            args: &["--width", "18446744073709551615", "1", "1"],
+            err: "seq: width greater than 65535: 18446744073709551615",
+        },
+        TestCase {
+            desc: "unknown padding",
+            args: &["--pad=left", "1"],
+            err: "seq: invalid padding: left",
+        },
+        TestCase {
+            desc: "format with width",
+            args: &["-f", "%g", "--width", "3", "1"],
+            err: "seq: --format may not be specified with --width",
+        },
+        TestCase {
+            desc: "format with pad",
+            args: &["-f", "%g", "--pad=space", "1"],
+            err: "seq: --format may not be specified with --pad",
+        },
+    ] {
+        match runseq(item.args) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                item.err,
+                "Should get error for {}",
+                item.desc
+            ),
+            Ok(_) => panic!("Should get error for {}", item.desc),
+        }
+    }
+}
+
+#[test]
+fn test_run_escapes() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "tab",
+            args: &["-s", "\\t", "3"],
+            exp: "1\t2\t3\t",
+        },
+        TestCase {
+            desc: "nul",
+            args: &["-s", "\\0", "3"],
+            exp: "1\x002\x003\x00",
+        },
+        TestCase {
+            desc: "newline terminator",
+            args: &["-s", ",", "-t", "\\n", "2"],
+            exp: "1,2,\n",
+        },
+        TestCase {
+            desc: "hex and unicode",
+            args: &["-s", "\\x2c\\u{20}", "2"],
+            exp: "1, 2, ",
+        },
+        TestCase {
+            desc: "backslash",
+            args: &["-s", "\\\\", "2"],
+            exp: "1\\2\\",
+        },
+        TestCase {
+            desc: "raw",
+            args: &["--raw", "-s", "\\t", "-t", "\\n", "2"],
+            exp: "1\\t2\\t\\n",
+        },
+        TestCase {
+            desc: "date nul",
+            args: &["--date", "-s", "\\0", "2026-01-01", "2026-01-02"],
+            exp: "2026-01-01\x002026-01-02\x00",
+        },
+    ] {
+        assert_eq!(
+            runseq(item.args)?,
+            item.exp,
+            "Invalid run output for {}",
+            item.desc
+        );
+    }
+
+    match run(
+        &mut vec![],
+        &["-t".to_string(), "\\xff".to_string(), "1".to_string()],
+    ) {
+        Err(e) => assert_eq!(e.to_string(), "seq: invalid escape sequence in '\\xff'"),
+        Ok(_) => panic!("Should get error for invalid escape"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_run_output() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "json",
+            args: &["--output", "json", "3"],
+            exp: "[1,2,3]\n",
+        },
+        TestCase {
+            desc: "json empty",
+            args: &["--output", "json", "-n", "0"],
+            exp: "[]\n",
+        },
+        TestCase {
+            desc: "json fractions",
+            args: &["--output", "json", "0", "0.5", "1"],
+            exp: "[0.0,0.5,1.0]\n",
+        },
+        TestCase {
+            desc: "json equal width",
+            args: &["--output", "json", "-w", "9", "10"],
+            exp: "[\"09\",10]\n",
+        },
+        TestCase {
+            desc: "json reverse",
+            args: &["--output", "json", "--reverse", "3"],
+            exp: "[3,2,1]\n",
+        },
+        TestCase {
+            desc: "json radix",
+            args: &["--output", "json", "-x", "--prefix", "15", "16"],
+            exp: "[\"0xf\",\"0x10\"]\n",
+        },
+        TestCase {
+            desc: "ndjson",
+            args: &["--output", "ndjson", "-n", "2", "--by", "0.5"],
+            exp: "{\"index\":0,\"value\":1.0}\n{\"index\":1,\"value\":1.5}\n",
+        },
+        TestCase {
+            desc: "ndjson reverse",
+            args: &["--output", "ndjson", "--reverse", "2"],
+            exp: "{\"index\":0,\"value\":2}\n{\"index\":1,\"value\":1}\n",
+        },
+        TestCase {
+            desc: "csv",
+            args: &["--output", "csv", "3"],
+            exp: "1\n2\n3\n",
+        },
+        TestCase {
+            desc: "csv header",
+            args: &["--output", "csv", "--header", "2"],
+            exp: "value\n1\n2\n",
+        },
+        TestCase {
+            desc: "csv named header",
+            args: &["--output", "csv", "--header=n", "2"],
+            exp: "n\n1\n2\n",
+        },
+        TestCase {
+            desc: "csv quoting",
+            args: &["--output", "csv", "-f", "%.0f,", "2"],
+            exp: "\"1,\"\n\"2,\"\n",
+        },
+        TestCase {
+            desc: "json date",
+            args: &["--output", "json", "--date", "2026-01-01", "2026-01-02"],
+            exp: "[\"2026-01-01\",\"2026-01-02\"]\n",
+        },
+        TestCase {
+            desc: "json geometric",
+            args: &["--output", "json", "--geometric", "1", "10", "100"],
+            exp: "[1,10,100]\n",
+        },
+    ] {
+        assert_eq!(
+            runseq(item.args)?,
+            item.exp,
+            "Invalid run output for {}",
+            item.desc
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bad_run_output() {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        err: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "unknown format",
+            args: &["--output", "xml", "3"],
+            err: "seq: invalid output format: xml",
+        },
+        TestCase {
+            desc: "json separator",
+            args: &["--output", "json", "-s,", "3"],
+            err: "seq: --output may not be specified with -s or -t",
+        },
+        TestCase {
+            desc: "csv terminator",
+            args: &["--output", "csv", "-t", ".", "3"],
+            err: "seq: --output may not be specified with -s or -t",
+        },
+        TestCase {
+            desc: "header without csv",
+            args: &["--header", "3"],
+            err: "seq: --header requires --output=csv",
+        },
+        TestCase {
+            desc: "header with json",
+            args: &["--output", "json", "--header", "3"],
+            err: "seq: --header requires --output=csv",
+        },
+    ] {
+        match runseq(item.args) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                item.err,
+                "Should get error for {}",
+                item.desc
+            ),
+            Ok(_) => panic!("Should get error for {}", item.desc),
+        }
+    }
+}
+
+#[test]
+fn test_run_product() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "two columns",
+            args: &["-s,", "--product", "2", "3"],
+            exp: "1 1,1 2,1 3,2 1,2 2,2 3,",
+        },
+        TestCase {
+            desc: "fraction column",
+            args: &["-s,", "--product", "1:2", "0:0.5:1"],
+            exp: "1 0.0,1 0.5,1 1.0,2 0.0,2 0.5,2 1.0,",
+        },
+        TestCase {
+            desc: "three columns",
+            args: &["-s,", "--product", "1:2", "1:2", "1:2"],
+            exp: "1 1 1,1 1 2,1 2 1,1 2 2,2 1 1,2 1 2,2 2 1,2 2 2,",
+        },
+        TestCase {
+            desc: "delimiter",
+            args: &["-s,", "--product", "-d", ":", "2", "2"],
+            exp: "1:1,1:2,2:1,2:2,",
+        },
+        TestCase {
+            desc: "equal width",
+            args: &["-s,", "--product", "-w", "9:10", "-1:1"],
+            exp: "09 -1,09 00,09 01,10 -1,10 00,10 01,",
+        },
+        TestCase {
+            desc: "decreasing column",
+            args: &["-s,", "--product", "2:1", "1"],
+            exp: "2 1,1 1,",
+        },
+        TestCase {
+            desc: "format",
+            args: &["-s,", "--product", "-f", "%.1f", "2", "1"],
+            exp: "1.0 1.0,2.0 1.0,",
+        },
+        TestCase {
+            desc: "radix column",
+            args: &["-s,", "--product", "0x1:0x2", "1"],
+            exp: "0x1 1,0x2 1,",
+        },
+        TestCase {
+            desc: "reverse",
+            args: &["-s,", "--product", "--reverse", "2", "2"],
+            exp: "2 2,2 1,1 2,1 1,",
+        },
+        TestCase {
+            desc: "one column",
+            args: &["-s,", "--product", "3"],
+            exp: "1,2,3,",
+        },
+        TestCase {
+            desc: "json",
+            args: &["--product", "--output=json", "2", "0:0.5:1"],
+            exp: "[[1,0.0],[1,0.5],[1,1.0],[2,0.0],[2,0.5],[2,1.0]]\n",
+        },
+        TestCase {
+            desc: "ndjson",
+            args: &["--product", "--output=ndjson", "2", "0x1"],
+            exp: "{\"index\":0,\"value\":[1,\"0x1\"]}\n{\"index\":1,\"value\":[2,\"0x1\"]}\n",
+        },
+        TestCase {
+            desc: "csv",
+            args: &["--product", "--output=csv", "--header=n", "2", "2"],
+            exp: "n1,n2\n1,1\n1,2\n2,1\n2,2\n",
+        },
+    ] {
+        assert_eq!(
+            runseq(item.args)?,
+            item.exp,
+            "Invalid run output for {}",
+            item.desc
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bad_run_product() {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        err: &'a str,
+    }
+
+    for item in [
+        TestCase {
+            desc: "no columns",
+            args: &["--product"],
+            err: usage!(),
+        },
+        TestCase {
+            desc: "long range",
+            args: &["--product", "1:2:3:4"],
+            err: "seq: invalid range: 1:2:3:4",
+        },
+        TestCase {
+            desc: "not a number",
+            args: &["--product", "1:x"],
+            err: "seq: invalid floating point argument: x",
+        },
+        TestCase {
+            desc: "zero increment",
+            args: &["--product", "1:0:2"],
+            err: "seq: zero increment",
+        },
+        TestCase {
+            desc: "count",
+            args: &["--product", "-n", "2", "1"],
+            err: "seq: --product may not be specified with -n",
+        },
+        TestCase {
+            desc: "geometric",
+            args: &["--product", "--geometric", "1:2"],
+            err: "seq: --product may not be specified with --geometric",
+        },
+        TestCase {
+            desc: "date",
+            args: &["--product", "--date", "1:2"],
+            err: "seq: --date may not be specified with --product",
+        },
+        TestCase {
+            desc: "output with delimiter",
+            args: &["--product", "--output=csv", "-d", ":", "2"],
+            err: "seq: --output may not be specified with -d",
+        },
+    ] {
+        match runseq(item.args) {
+            Err(e) => assert_eq!(
+                e.to_string(),
+                item.err,
+                "Should get error for {}",
+                item.desc
+            ),
+            Ok(_) => panic!("Should get error for {}", item.desc),
+        }
+    }
+}
+
+#[test]
+fn test_run_help() -> Result<()> {
+    struct TestCase<'a> {
+        desc: &'a str,
+        args: &'a [&'a str],
+        exp: String,
+    }
+
+    let opts = options();
+    for item in [
+        TestCase {
+            desc: "help",
+            args: &["--help"],
+            exp: opts.usage(usage!()),
+        },
+        TestCase {
+            desc: "help with operands",
+            args: &["--help", "1", "3"],
+            exp: opts.usage(usage!()),
+        },
+        TestCase {
+            desc: "help with bad option value",
+            args: &["-w", "--help", "--compat=nope"],
+            exp: opts.usage(usage!()),
+        },
+        TestCase {
+            desc: "version",
+            args: &["--version"],
+            exp: format!("crab seq {}\n", env!("CARGO_PKG_VERSION")),
+        },
+    ] {
+        assert_eq!(
+            runseq(item.args)?,
+            item.exp,
+            "Invalid run output for {}",
+            item.desc
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_utility() {
+    for (args, out, err, code) in [
+        (vec!["-s,", "3"], "1,2,3,", "", 0),
+        (vec!["1", "0", "3"], "", "seq: zero increment\n", 1),
+        (
+            vec!["--bogus"],
+            "",
+            concat!(
+                "seq: Unrecognized option: 'bogus'\n",
+                usage!(),
+                "\nTry 'seq --help' for more information.\n"
+            ),
+            2,
+        ),
+    ] {
+        let args: Vec<String> = args.into_iter().map(String::from).collect();
+        let (mut obuf, mut ebuf): (Vec<u8>, Vec<u8>) = (vec![], vec![]);
+        assert_eq!(SeqUtility.name(), "seq");
+        assert_eq!(
+            SeqUtility.run(&mut obuf, &mut ebuf, &args),
+            ExitCode::from(code),
+            "Should exit for {:?}",
+            args
+        );
+        assert_eq!(
+            out,
+            String::from_utf8(obuf).unwrap(),
+            "Output for {:?}",
+            args
+        );
+        assert_eq!(
+            err,
+            String::from_utf8(ebuf).unwrap(),
+            "Errors for {:?}",
+            args
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use std::convert::TryFrom;
+
+// The number of Feistel rounds used to permute an integer.
+const ROUNDS: usize = 4;
+
+/// Permutation iterates over a pseudorandom permutation of the integers in
+/// 0..n without storing them. It encrypts each integer with a small Feistel
+/// network over a domain of 4^k >= n integers, and re-encrypts any result not
+/// less than n until one is (a technique known as cycle walking). The same n
+/// and seed always produce the same permutation.
+pub struct Permutation {
+    n: u64,
+    next: u64,
+    // The number of bits in each half of an integer in the domain.
+    half: u32,
+    keys: [u64; ROUNDS],
+}
+
+impl Permutation {
+    pub fn new(n: u64, seed: u64) -> Permutation {
+        let bits = 64 - n.saturating_sub(1).leading_zeros();
+        let mut state = seed;
+        Permutation {
+            n,
+            next: 0,
+            half: bits.div_ceil(2).max(1),
+            keys: [(); ROUNDS].map(|_| splitmix(&mut state)),
+        }
+    }
+
+    fn encrypt(&self, x: u64) -> u64 {
+        let mask = (1u64 << self.half) - 1;
+        let (mut left, mut right) = (x >> self.half, x & mask);
+        for key in self.keys {
+            let next = left ^ (mix(right ^ key) & mask);
+            left = right;
+            right = next;
+        }
+        (left << self.half) | right
+    }
+}
+
+impl Iterator for Permutation {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.next >= self.n {
+            return None;
+        }
+        let mut x = self.encrypt(self.next);
+        while x >= self.n {
+            x = self.encrypt(x);
+        }
+        self.next += 1;
+        Some(x)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = usize::try_from(self.n - self.next).ok();
+        (len.unwrap_or(usize::MAX), len)
+    }
+}
+
+// Advances the state and returns a pseudorandom number from it (SplitMix64).
+fn splitmix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    mix(*state)
+}
+
+// Scrambles the bits of z.
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[path = "shuffle_test.rs"]
+#[cfg(test)]
+mod test;
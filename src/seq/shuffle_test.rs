@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn test_permutation() {
+    for n in [0, 1, 2, 3, 4, 5, 10, 16, 17, 100, 1000, 4097] {
+        for seed in [0, 1, 42, u64::MAX] {
+            let mut items: Vec<u64> = Permutation::new(n, seed).collect();
+            assert_eq!(
+                items.len() as u64,
+                n,
+                "Should have {} items with seed {}",
+                n,
+                seed
+            );
+            items.sort_unstable();
+            assert!(
+                items.iter().copied().eq(0..n),
+                "Should permute 0..{} with seed {}",
+                n,
+                seed
+            );
+        }
+    }
+}
+
+#[test]
+fn test_permutation_seed() {
+    let perm = |seed| Permutation::new(1000, seed).collect::<Vec<_>>();
+    assert_eq!(
+        perm(42),
+        perm(42),
+        "Same seed should produce same permutation"
+    );
+    assert_ne!(perm(42), perm(43), "Different seeds should differ");
+    assert_ne!(perm(42), (0..1000).collect::<Vec<_>>(), "Should shuffle");
+}
+
+#[test]
+fn test_permutation_huge() {
+    // Should not allocate for huge ranges.
+    let items: Vec<u64> = Permutation::new(u64::MAX, 7).take(3).collect();
+    assert_eq!(items.len(), 3);
+    assert!(items.iter().all(|x| *x < u64::MAX));
+    assert_eq!(Permutation::new(10, 1).size_hint(), (10, Some(10)));
+}